@@ -0,0 +1,210 @@
+//! Status bar integrations, printed by `sj bar`.
+
+use std::{
+    io::{Write, stdout},
+    slice,
+    time::Duration,
+};
+
+use anyhow::Result;
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta};
+use clap::ValueEnum;
+use colored::control;
+use serde_json::json;
+use subjective::{Subjective, color::Color, render::Cell, school::bells::BellTime};
+use tokio::time::sleep;
+
+/// Longest time to wait before checking the time again in continuous mode, so that updates
+/// aren't missed when the computer sleeps.
+const MAX_WAIT: Duration = Duration::from_mins(1);
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum BarFormat {
+    #[value(help = "JSON for a Waybar custom module with `\"return-type\": \"json\"`")]
+    Waybar,
+    #[value(help = "Lines for an i3blocks blocklet, with `interval=persist` in continuous mode")]
+    I3blocks,
+    #[value(
+        help = "Text with color tags for a Polybar script module, with `tail = true` in continuous mode"
+    )]
+    Polybar,
+    #[value(help = "Text with style tags for the tmux status line, like `#(sj bar -f tmux)`")]
+    Tmux,
+}
+
+/// What to show in a status bar.
+struct Status {
+    /// Text, like `Yr 10 English in C17 until 10:01 AM`.
+    text: String,
+    /// Shorter text, like `Yr 10 English`.
+    short: String,
+    /// The rest of the day's bells, one per line.
+    tooltip: String,
+    /// Kind of the status, like `class`, `break`, `next`, or `no-school`.
+    class: String,
+    /// Color of the subject of a class.
+    color: Option<Color>,
+}
+
+/// Get the title and color of a bell, which are the subject's for a class.
+fn title(data: &Subjective, bell: &BellTime) -> (String, Option<Color>, String) {
+    let cell = Cell::from_day(data, slice::from_ref(bell))
+        .ok()
+        .and_then(|cells| cells.first().copied());
+    cell.map_or_else(
+        || (bell.name.clone(), None, String::new()),
+        |cell| {
+            (
+                cell.title().to_string(),
+                cell.subject.map(|subject| subject.color),
+                cell.location.to_string(),
+            )
+        },
+    )
+}
+
+fn status(data: &Subjective, now: NaiveDateTime, variant_offset: usize) -> Status {
+    let today = now.date();
+    let time = |time: NaiveTime| time.format("%-I:%M %p").to_string();
+    let tooltip = data
+        .bells_from(now, variant_offset)
+        .take_while(|(date, _)| *date == today)
+        .filter_map(|(_, bell)| bell.format_with_time(data).ok())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Ok(period) = data.current_period(now, variant_offset) {
+        let (title, color, location) = title(data, period.bell);
+        let place = if location.is_empty() {
+            String::new()
+        } else {
+            format!(" in {location}")
+        };
+        return Status {
+            text: format!("{title}{place} until {}", time(period.end)),
+            short: title,
+            tooltip,
+            class: period.bell.bell_data.as_ref().map_or_else(
+                || "time".to_string(),
+                |bell_data| bell_data.to_string().to_lowercase(),
+            ),
+            color,
+        };
+    }
+    let no_school = data.get_override(today).is_none()
+        && (data.school.calendar.no_school_reason(today).is_some()
+            || matches!(data.get_day(today, variant_offset), Ok(None)));
+    let Some((bell_date, bell)) = data.bells_from(now, variant_offset).next() else {
+        return Status {
+            text: "No upcoming bells".to_string(),
+            short: String::new(),
+            tooltip,
+            class: "none".to_string(),
+            color: None,
+        };
+    };
+    let (title, color, _) = title(data, bell);
+    let weekday = if bell_date == today {
+        String::new()
+    } else {
+        format!("{} ", bell_date.format("%a"))
+    };
+    Status {
+        text: format!("Next {title} at {weekday}{}", time(bell.time)),
+        short: title,
+        tooltip,
+        class: if no_school { "no-school" } else { "next" }.to_string(),
+        color,
+    }
+}
+
+impl BarFormat {
+    /// Format a status in the bar's protocol. In continuous mode, every update is a single line.
+    fn format(self, status: &Status, continuous: bool) -> String {
+        match self {
+            Self::Waybar => {
+                let escape = |text: &str| {
+                    text.replace('&', "&amp;")
+                        .replace('<', "&lt;")
+                        .replace('>', "&gt;")
+                };
+                json!({
+                    "text": escape(&status.text),
+                    "alt": status.short,
+                    "tooltip": escape(&status.tooltip),
+                    "class": status.class,
+                })
+                .to_string()
+            }
+            Self::I3blocks if continuous => status.text.clone(),
+            Self::I3blocks => {
+                let mut lines = vec![status.text.clone(), status.short.clone()];
+                lines.extend(status.color.map(|color| color.to_hex()));
+                lines.join("\n")
+            }
+            Self::Polybar => status.color.map_or_else(
+                || status.text.clone(),
+                |color| format!("%{{F{}}}{}%{{F-}}", color.to_hex(), status.text),
+            ),
+            Self::Tmux => {
+                let text = status.text.replace('#', "##");
+                match status.color {
+                    Some(color) => format!("#[fg={}]{text}#[default]", color.to_hex()),
+                    None => text,
+                }
+            }
+        }
+    }
+}
+
+/// Get the first enabled bell strictly after a time, when the status changes.
+fn next_change(
+    data: &Subjective,
+    now: NaiveDateTime,
+    variant_offset: usize,
+) -> Option<NaiveDateTime> {
+    data.bells_from(now, variant_offset)
+        .map(|(date, bell)| date.and_time(bell.time))
+        .find(|time| *time > now)
+}
+
+/// Print the status of the school in a status bar's protocol. The time is given by `clock`, in
+/// the school's local time.
+///
+/// In continuous mode, an update is printed whenever a bell rings, by waiting until the next
+/// bell. Updates are only printed when the status changes, and the time is checked at least
+/// every minute, so that bells aren't missed when the computer sleeps.
+pub async fn run(
+    data: &Subjective,
+    variant_offset: usize,
+    format: BarFormat,
+    continuous: bool,
+    clock: impl Fn(&Subjective) -> NaiveDateTime,
+) -> Result<()> {
+    control::set_override(false);
+    let mut stdout = stdout();
+    let mut last = None;
+    loop {
+        let now = clock(data);
+        let output = format.format(&status(data, now, variant_offset), continuous);
+        if last.as_ref() != Some(&output) {
+            if writeln!(stdout, "{output}")
+                .and_then(|()| stdout.flush())
+                .is_err()
+            {
+                return Ok(());
+            }
+            last = Some(output);
+        }
+        if !continuous {
+            return Ok(());
+        }
+        let wait = next_change(data, now, variant_offset).map_or(MAX_WAIT, |next| {
+            data.duration_between(now, next)
+                .max(TimeDelta::zero())
+                .to_std()
+                .unwrap_or(MAX_WAIT)
+                .min(MAX_WAIT)
+        });
+        sleep(wait).await;
+    }
+}
@@ -7,30 +7,65 @@
 )]
 #![allow(clippy::multiple_crate_versions, clippy::cargo_common_metadata)]
 
+mod bar;
+mod output;
+mod tui;
+
+use bar::BarFormat;
 use humantime::format_duration;
 use indoc::formatdoc;
 use log::info;
+use output::{
+    Activation, CurrentPeriod, DayStatus, Now, OutputFormat, Override, Problem, TimetableDay,
+    TimetableWeek, print_json, print_tsv, timestamp,
+};
 use serde::{Deserialize, Serialize};
 use shellexpand::full;
 use std::borrow::Cow;
-use std::iter::repeat;
+use std::fs::metadata;
+use std::io::{Write as _, stdout};
+use std::mem::take;
 use std::path::PathBuf;
+use std::pin::pin;
+use std::ptr;
+use std::time::{Duration, Instant, SystemTime};
 use std::{fmt::Write, path::Path};
-use subjective::get_current_variant;
+use subjective::binary::to_binary;
+use subjective::color::Color;
+use subjective::export::csv::to_csv;
+use subjective::export::ical::to_ical;
+use subjective::import::csv::from_csv;
+use subjective::import::ical::from_ical;
+use subjective::migration::{CURRENT_VERSION, Migration};
+use subjective::normalization::Change;
+use subjective::render::{Cell, Grid, html::to_html, markdown::to_markdown};
+use subjective::schema::{catalog_schema, data_schema};
+use subjective::school::Day;
+use subjective::school::activation::VariantActivation;
 use subjective::school::bells::BellTime;
-use subjective::school::Week;
+use subjective::school::cycle::CycleAnchor;
+use subjective::school::overrides::{DayOverride, Replacement};
+use subjective::school::period::Period;
+use subjective::source::compile;
+use subjective::validation::{Diagnostic, Severity};
 
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, Datelike, Local, Weekday};
-use clap::{Args, Parser, Subcommand};
-use colored::Colorize;
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta,
+    Timelike, Utc, Weekday,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use colored::{ColoredString, Colorize};
 use directories::ProjectDirs;
 
 use env_logger::init;
 use inquire::{InquireError, Select};
 use reqwest::get;
-use subjective::{school::School, Subjective};
+use subjective::{LoadDataError, Subjective, school::School};
 use tokio::fs::{create_dir_all, read_to_string, write, File};
+use tokio::select;
+use tokio::signal::ctrl_c;
+use tokio::time::{MissedTickBehavior, interval};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -44,22 +79,112 @@ struct Cli {
     #[arg(
         short,
         long,
-        help = "Use a custom time instead of the current time, which must be able to be `std::str::FromStr`'d into a `chrono::datetime::DateTime<chrono::offset::Local>`.",
-        global = true
+        help = "Use a custom time instead of the current time. Times with an offset, like `2026-01-26T09:00:00+11:00`, are converted to the school's timezone, and times without one, like `2026-01-26T09:00:00`, are in the school's local time.",
+        global = true,
+        value_parser = parse_time
+    )]
+    time: Option<Time>,
+    #[arg(
+        long = "output",
+        id = "output_format",
+        help = "Format of the output of `now`, `data check`, `timetable show`, and listings",
+        global = true,
+        value_enum,
+        default_value_t
     )]
-    time: Option<DateTime<Local>>,
+    output: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Time {
+    Instant(DateTime<FixedOffset>),
+    SchoolLocal(NaiveDateTime),
+}
+
+fn parse_time(time: &str) -> Result<Time, String> {
+    time.parse()
+        .map(Time::Instant)
+        .or_else(|_| time.parse().map(Time::SchoolLocal))
+        .map_err(|_| format!("\"{time}\" isn't a valid time, like `2026-01-26T09:00:00+11:00`."))
+}
+
+/// Get the current time like [`school_now`], to the second, for commands which keep running. A
+/// time given with `--time` advances from `start`.
+fn running_now(data: &Subjective, time: Option<Time>, start: Instant) -> NaiveDateTime {
+    let mut now = school_now(data, time);
+    if time.is_some() {
+        now += TimeDelta::from_std(start.elapsed()).unwrap_or_default();
+    }
+    now.with_nanosecond(0).unwrap_or(now)
+}
+
+/// Get the current time, or the time given with `--time`, in the school's local time.
+fn school_now(data: &Subjective, time: Option<Time>) -> NaiveDateTime {
+    match time {
+        Some(Time::Instant(time)) => data.school_time(&time),
+        Some(Time::SchoolLocal(time)) => time,
+        None => data.school_time(&Local::now()),
+    }
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     #[command(visible_alias = "n", about = "View time information")]
-    Now,
+    Now(NowArgs),
     #[command(visible_alias = "d", about = "Configure Subjective data")]
     Data(DataArgs),
     #[command(visible_alias = "t", about = "View timetable information")]
     Timetable(TimetableArgs),
     #[command(visible_alias = "c", about = "Configure Subjective settings")]
     Config(ConfigArgs),
+    #[command(
+        visible_alias = "b",
+        about = "Print the current or next class for a status bar, like Waybar or tmux"
+    )]
+    Bar {
+        #[arg(short, long, help = "Status bar to print for")]
+        format: BarFormat,
+        #[arg(
+            short,
+            long,
+            help = "Keep running, printing an update whenever a bell rings"
+        )]
+        continuous: bool,
+    },
+    #[command(
+        about = "Show a full-screen dashboard of the current period, upcoming bells, notices, links, and timetable"
+    )]
+    Tui,
+}
+
+impl Commands {
+    /// Whether the command prints JSON or TSV, as selected by `--output`.
+    const fn has_formatted_output(&self) -> bool {
+        matches!(
+            self,
+            Self::Now(NowArgs { watch: false })
+                | Self::Data(DataArgs {
+                    command: DataCommands::Check,
+                })
+                | Self::Timetable(TimetableArgs {
+                    command: TimetableCommands::Show(_)
+                        | TimetableCommands::Activations
+                        | TimetableCommands::Override(OverrideArgs {
+                            command: OverrideCommands::List,
+                        }),
+                })
+        )
+    }
+}
+
+#[derive(Args, Debug, Default)]
+struct NowArgs {
+    #[arg(
+        short,
+        long,
+        help = "Keep showing the time information, updating it every second until Ctrl-C is pressed"
+    )]
+    watch: bool,
 }
 
 #[derive(Args, Debug)]
@@ -79,15 +204,127 @@ enum DataCommands {
             default_value = SUBJECTIVEKIT_URL
         )]
         server: String,
+        #[arg(
+            short,
+            long,
+            help = "Sort bells, remove duplicates, and assign stable UUIDs before saving"
+        )]
+        normalize: bool,
+    },
+
+    #[command(
+        visible_aliases = ["l", "import"],
+        about = "Load school and subjects from a local JSON, iCalendar (.ics), or CSV file"
+    )]
+    Load {
+        #[arg(help = "File to load, or directory with bells.csv and subjects.csv for CSV")]
+        file: PathBuf,
+        #[arg(
+            short,
+            long,
+            help = "Format to load from, defaults to the file's extension"
+        )]
+        format: Option<ImportFormat>,
+        #[arg(
+            short,
+            long,
+            help = "Sort bells, remove duplicates, and assign stable UUIDs before saving"
+        )]
+        normalize: bool,
+    },
+
+    #[command(
+        visible_alias = "c",
+        about = "Check data for problems, exiting with an error if any are found"
+    )]
+    Check,
+
+    #[command(
+        visible_alias = "m",
+        about = "Migrate saved data to the current version of the data format"
+    )]
+    Migrate,
+
+    #[command(
+        visible_alias = "b",
+        about = "Build data from a TOML timetable source, checking it for problems before saving"
+    )]
+    Build {
+        file: PathBuf,
+        #[arg(
+            short,
+            long = "out",
+            help = "File to write the JSON data to, instead of saving it"
+        )]
+        output: Option<PathBuf>,
     },
 
     #[command(
-        visible_alias = "l",
-        about = "Load school and subjects from local file"
+        visible_alias = "s",
+        about = "Print the JSON Schema of Subjective data files, for editors and other tools"
     )]
-    Load { file: PathBuf },
+    Schema {
+        #[arg(
+            long,
+            help = "Print the schema of SubjectiveKit catalogs, like schools.json"
+        )]
+        catalog: bool,
+        #[arg(
+            short,
+            long = "out",
+            help = "File to write to, defaults to standard output"
+        )]
+        output: Option<PathBuf>,
+    },
+
+    #[command(visible_alias = "e", about = "Export the timetable to another format")]
+    Export {
+        #[arg(short, long, help = "Format to export to")]
+        format: ExportFormat,
+        #[arg(long, help = "First date to export, defaults to today")]
+        from: Option<NaiveDate>,
+        #[arg(long, help = "Last date to export, inclusive, required for iCalendar")]
+        to: Option<NaiveDate>,
+        #[arg(
+            short,
+            long = "out",
+            help = "File to write to, defaults to standard output, or directory for CSV"
+        )]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ExportFormat {
+    #[value(help = "iCalendar, for calendar apps")]
+    Ics,
+    #[value(help = "CSV sheets of bells and subjects, for spreadsheets")]
+    Csv,
+    #[value(help = "Compact binary timetable, for bell displays and other devices")]
+    Binary,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum RenderFormat {
+    #[value(help = "HTML page, for printing")]
+    Html,
+    #[value(alias = "markdown", help = "Markdown, for notes apps")]
+    Md,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportFormat {
+    #[value(help = "Subjective data, like a .subjective file")]
+    Json,
+    #[value(help = "iCalendar, from calendar apps")]
+    Ics,
+    #[value(help = "CSV sheets of bells and subjects, from spreadsheets")]
+    Csv,
+}
+
+const CSV_BELLS_FILE: &str = "bells.csv";
+const CSV_SUBJECTS_FILE: &str = "subjects.csv";
+
 #[derive(Args, Debug)]
 struct TimetableArgs {
     #[command(subcommand)]
@@ -97,7 +334,130 @@ struct TimetableArgs {
 #[derive(Subcommand, Debug)]
 enum TimetableCommands {
     #[command(visible_alias = "s", about = "Show timetable")]
-    Show,
+    Show(ShowArgs),
+    #[command(
+        visible_alias = "e",
+        about = "Export the timetable as a weekly grid, for printing or notes apps"
+    )]
+    Export {
+        #[arg(short, long, help = "Format to export to")]
+        format: RenderFormat,
+        #[arg(
+            short,
+            long = "out",
+            help = "File to write to, defaults to standard output"
+        )]
+        output: Option<PathBuf>,
+    },
+    #[command(
+        visible_alias = "a",
+        about = "Activate a week variant for a range of dates, overriding the automatic cycle"
+    )]
+    Activate {
+        #[arg(help = "Name of the week variant to activate")]
+        variant: String,
+        #[arg(long, help = "First date the variant is active on")]
+        from: NaiveDate,
+        #[arg(long, help = "Last date the variant is active on, inclusive")]
+        to: NaiveDate,
+    },
+    #[command(about = "List week variant activations")]
+    Activations,
+    #[command(about = "Remove a week variant activation")]
+    Deactivate {
+        #[arg(help = "Number of the activation, as shown by `sj timetable activations`")]
+        number: usize,
+    },
+    #[command(about = "Anchor the week variant cycle, so that the week of a date is a variant")]
+    Anchor {
+        #[arg(help = "Name of the cyclical week variant which is active in the anchored week")]
+        variant: String,
+        #[arg(long, help = "Any date in the anchored week, defaults to today")]
+        date: Option<NaiveDate>,
+    },
+    #[command(
+        visible_alias = "o",
+        about = "Manage date-specific overrides of the timetable"
+    )]
+    Override(OverrideArgs),
+    #[command(
+        visible_alias = "tz",
+        about = "Show or set the timezone of the school, like \"Australia/Sydney\""
+    )]
+    Timezone {
+        #[arg(help = "IANA name of the timezone to set")]
+        timezone: Option<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct ShowArgs {
+    #[arg(
+        short,
+        long,
+        help = "Name of the week variant to show, defaults to every variant",
+        conflicts_with_all = ["today", "week"]
+    )]
+    variant: Option<String>,
+    #[arg(
+        long,
+        help = "Only show today's bells, including overrides",
+        conflicts_with = "week"
+    )]
+    today: bool,
+    #[arg(
+        short,
+        long,
+        help = "Only show the week variant which is active this week"
+    )]
+    week: bool,
+    #[arg(short = 'H', long, help = "Highlight the current period")]
+    highlight: bool,
+}
+
+#[derive(Args, Debug)]
+struct OverrideArgs {
+    #[command(subcommand)]
+    command: OverrideCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum OverrideCommands {
+    #[command(visible_alias = "a", about = "Override the bells of a date")]
+    Add {
+        #[arg(help = "Date to override")]
+        date: NaiveDate,
+        #[arg(short, long, help = "Description of the override", default_value = "")]
+        name: String,
+        #[arg(
+            short,
+            long,
+            help = "Follow the bells of this weekday",
+            required_unless_present = "bells",
+            conflicts_with = "bells"
+        )]
+        weekday: Option<Weekday>,
+        #[arg(
+            short,
+            long,
+            help = "Name of the week variant to follow, defaults to the active variant",
+            requires = "weekday"
+        )]
+        variant: Option<String>,
+        #[arg(
+            short,
+            long,
+            help = "Follow the bells in a JSON file, which is a list of bells in the same format as the Subjective data file"
+        )]
+        bells: Option<PathBuf>,
+    },
+    #[command(visible_alias = "l", about = "List overrides")]
+    List,
+    #[command(visible_alias = "r", about = "Remove the overrides of a date")]
+    Remove {
+        #[arg(help = "Date to remove the overrides of")]
+        date: NaiveDate,
+    },
 }
 
 #[derive(Args, Debug)]
@@ -110,6 +470,11 @@ struct ConfigArgs {
 enum ConfigCommands {
     #[command(visible_alias = "i", about = "Initialise configuration")]
     Init,
+    #[command(
+        visible_alias = "m",
+        about = "Migrate the `variant_offset` setting to a cycle anchor in the Subjective data"
+    )]
+    Migrate,
 }
 
 const REPO: &str = env!("CARGO_PKG_REPOSITORY");
@@ -127,52 +492,751 @@ async fn main() -> Result<()> {
             ))
         })?;
     let config_directory = config_directory.config_dir();
-    let time = cli.time.unwrap_or_else(Local::now);
-    match cli.command.unwrap_or(Commands::Now) {
-        Commands::Now => {
-            now(config_directory, time).await?;
+    let time = cli.time;
+    let output = cli.output;
+    let command = cli
+        .command
+        .unwrap_or_else(|| Commands::Now(NowArgs::default()));
+    if output != OutputFormat::Text && !command.has_formatted_output() {
+        return Err(anyhow!(
+            "Only `now` without --watch, `data check`, `timetable show`, `timetable activations`, and `timetable override list` support --output {}.",
+            output
+                .to_possible_value()
+                .map_or_else(String::new, |value| value.get_name().to_string())
+        ));
+    }
+    match command {
+        Commands::Now(NowArgs { watch }) => {
+            now(config_directory, time, output, watch).await?;
+        }
+        Commands::Data(DataArgs { command }) => {
+            data(command, config_directory, time, output).await?;
         }
-        Commands::Data(DataArgs { command }) => match command {
-            DataCommands::Pull { server } => {
-                pull(&server, config_directory).await?;
+        Commands::Timetable(TimetableArgs { command }) => match command {
+            TimetableCommands::Show(args) => {
+                show(config_directory, &args, time, output).await?;
             }
-            DataCommands::Load { file } => {
-                let file = file.display().to_string();
-                load(
-                    &full(&file).unwrap_or(Cow::Borrowed(&file)),
-                    config_directory,
-                )
-                .await?;
+            TimetableCommands::Export { format, output } => {
+                export_timetable(config_directory, format, output.as_deref()).await?;
             }
-        },
-        Commands::Timetable(TimetableArgs { command }) => match command {
-            TimetableCommands::Show => {
-                show(config_directory).await?;
+            TimetableCommands::Activate { variant, from, to } => {
+                activate(config_directory, &variant, from, to).await?;
+            }
+            TimetableCommands::Activations => {
+                activations(config_directory, output)?;
+            }
+            TimetableCommands::Deactivate { number } => {
+                deactivate(config_directory, number).await?;
+            }
+            TimetableCommands::Override(OverrideArgs { command }) => match command {
+                OverrideCommands::Add {
+                    date,
+                    name,
+                    weekday,
+                    variant,
+                    bells,
+                } => {
+                    add_override(
+                        config_directory,
+                        date,
+                        name,
+                        weekday,
+                        variant.as_deref(),
+                        bells.as_deref(),
+                    )
+                    .await?;
+                }
+                OverrideCommands::List => {
+                    list_overrides(config_directory, output)?;
+                }
+                OverrideCommands::Remove { date } => {
+                    remove_override(config_directory, date).await?;
+                }
+            },
+            TimetableCommands::Timezone { timezone } => {
+                set_timezone(config_directory, timezone).await?;
+            }
+            TimetableCommands::Anchor { variant, date } => {
+                anchor(config_directory, &variant, date, time).await?;
             }
         },
+        Commands::Bar { format, continuous } => {
+            bar(config_directory, time, format, continuous).await?;
+        }
+        Commands::Tui => {
+            tui(config_directory, time).await?;
+        }
         Commands::Config(ConfigArgs { command }) => match command {
             ConfigCommands::Init => {
                 init_config(config_directory).await?;
             }
+            ConfigCommands::Migrate => {
+                migrate_config(config_directory, time).await?;
+            }
         },
     }
     Ok(())
 }
 
-async fn show(config_directory: &Path) -> Result<()> {
+async fn data(
+    command: DataCommands,
+    config_directory: &Path,
+    time: Option<Time>,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        DataCommands::Pull { server, normalize } => {
+            pull(&server, config_directory, normalize).await?;
+        }
+        DataCommands::Load {
+            file,
+            format,
+            normalize,
+        } => {
+            let file = file.display().to_string();
+            load(
+                &full(&file).unwrap_or(Cow::Borrowed(&file)),
+                format,
+                config_directory,
+                normalize,
+            )
+            .await?;
+        }
+        DataCommands::Check => {
+            check(config_directory, format)?;
+        }
+        DataCommands::Migrate => {
+            migrate(config_directory).await?;
+        }
+        DataCommands::Build { file, output } => {
+            let file = file.display().to_string();
+            build(
+                &full(&file).unwrap_or(Cow::Borrowed(&file)),
+                output.as_deref(),
+                config_directory,
+            )
+            .await?;
+        }
+        DataCommands::Schema { catalog, output } => {
+            schema(catalog, output.as_deref()).await?;
+        }
+        DataCommands::Export {
+            format,
+            from,
+            to,
+            output,
+        } => {
+            export(config_directory, format, from, to, output.as_deref(), time).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Maximum width of a column of a timetable grid, in characters.
+const COLUMN_WIDTH: usize = 24;
+
+/// Truncate or pad text to a width, in characters.
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        let text = text.chars().take(width - 1).collect::<String>();
+        format!("{text}…")
+    } else {
+        format!("{text:width$}")
+    }
+}
+
+/// Get the lines of a cell of a timetable grid, styled with the subject's color, dimmed if the
+/// bell is disabled, and reversed if it starts the current period.
+fn cell_lines(cell: &Cell, width: usize, current: Option<&BellTime>) -> Vec<ColoredString> {
+    let title = fit(cell.title(), width);
+    let mut lines = vec![cell.subject.map_or_else(
+        || title.bold(),
+        |subject| subject.color.color(title.as_str()).bold(),
+    )];
+    if cell.subject.is_some() {
+        let details = if cell.location.is_empty() {
+            cell.bell.name.clone()
+        } else {
+            format!("{} · {}", cell.bell.name, cell.location)
+        };
+        lines.push(fit(&details, width).dimmed());
+    }
+    lines.push(fit(&cell.time(), width).dimmed());
+    lines
+        .into_iter()
+        .map(|line| {
+            let line = if cell.bell.enabled {
+                line
+            } else {
+                line.dimmed()
+            };
+            if current.is_some_and(|current| ptr::eq(current, cell.bell)) {
+                line.reversed()
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+/// Print a timetable grid, with a column per header. The header of today's column is highlighted.
+fn print_grid(
+    headers: &[&str],
+    rows: &[Vec<Option<Cell>>],
+    today: Option<usize>,
+    current: Option<&BellTime>,
+) {
+    let width = rows
+        .iter()
+        .flatten()
+        .flatten()
+        .flat_map(|cell| {
+            [
+                cell.title().chars().count(),
+                cell.bell.name.chars().count() + cell.location.chars().count() + 3,
+                cell.time().chars().count(),
+            ]
+        })
+        .chain(headers.iter().map(|header| header.chars().count()))
+        .max()
+        .unwrap_or_default()
+        .min(COLUMN_WIDTH);
+    let separator = format!(" {} ", "│".dimmed());
+    let rule = vec!["─".repeat(width); headers.len()].join("─┼─");
+    let headers = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            let header = fit(header, width).bold();
+            if today == Some(column) {
+                Color::SUBJECTIVE_BLUE.color(header)
+            } else {
+                header
+            }
+        })
+        .map(|header| header.to_string())
+        .collect::<Vec<_>>();
+    println!("{}", headers.join(&separator));
+    for row in rows {
+        println!("{}", rule.dimmed());
+        let cells = row
+            .iter()
+            .map(|cell| {
+                cell.as_ref()
+                    .map(|cell| cell_lines(cell, width, current))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        for line in 0..cells.iter().map(Vec::len).max().unwrap_or_default() {
+            let line = cells
+                .iter()
+                .map(|cell| {
+                    cell.get(line)
+                        .map_or_else(|| " ".repeat(width), ToString::to_string)
+                })
+                .collect::<Vec<_>>();
+            println!("{}", line.join(&separator));
+        }
+    }
+}
+
+/// Get the week variants shown by `sj timetable show`.
+fn shown_variants(
+    data: &Subjective,
+    args: &ShowArgs,
+    current_variant: Option<usize>,
+) -> Result<Vec<usize>> {
+    Ok(match (&args.variant, args.week) {
+        (Some(variant), _) => vec![find_variant(data, variant)?],
+        (None, true) => {
+            vec![current_variant.ok_or_else(|| anyhow!("No week variant is active this week."))?]
+        }
+        (None, false) => (0..data.school.bell_times.len()).collect(),
+    })
+}
+
+fn print_timetable(
+    data: &Subjective,
+    args: &ShowArgs,
+    today: NaiveDate,
+    variant_offset: usize,
+    format: OutputFormat,
+) -> Result<()> {
+    if args.today {
+        let day = TimetableDay {
+            weekday: weekday_name(today.weekday()).to_string(),
+            date: Some(today),
+            bells: Cell::from_day(
+                data,
+                data.get_day(today, variant_offset)?
+                    .map_or(&[], Vec::as_slice),
+            )?
+            .iter()
+            .map(|cell| output::Bell::from_cell(data, cell, Some(today)))
+            .collect(),
+        };
+        return match format {
+            OutputFormat::Tsv => {
+                print_tsv(&TimetableDay::header(), &day.rows());
+                Ok(())
+            }
+            _ => print_json(&day),
+        };
+    }
+    let current_variant = data.current_variant(today, variant_offset);
+    let weeks = shown_variants(data, args, current_variant)?
+        .into_iter()
+        .map(|variant| {
+            let week = &data.school.bell_times[variant];
+            let days = Grid::new(data, week)?
+                .weekdays
+                .iter()
+                .map(|weekday| {
+                    Ok(TimetableDay {
+                        weekday: weekday_name(*weekday).to_string(),
+                        date: None,
+                        bells: Cell::from_day(data, &week.days[*weekday])?
+                            .iter()
+                            .map(|cell| output::Bell::from_cell(data, cell, None))
+                            .collect(),
+                    })
+                })
+                .collect::<Result<_>>()?;
+            Ok(TimetableWeek {
+                name: week.name.clone(),
+                this_week: current_variant == Some(variant),
+                days,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    match format {
+        OutputFormat::Tsv => {
+            print_tsv(&TimetableWeek::header(), &TimetableWeek::rows(&weeks));
+            Ok(())
+        }
+        _ => print_json(&weeks),
+    }
+}
+
+async fn show(
+    config_directory: &Path,
+    args: &ShowArgs,
+    time: Option<Time>,
+    format: OutputFormat,
+) -> Result<()> {
+    let config = get_config(config_directory).await?;
     let data = Subjective::from_config(config_directory)?;
-    dbg!(&data);
-    let () = async {}.await;
-    println!("Timetable for {}", data.school.name);
+    let now = school_now(&data, time);
+    let today = now.date();
+    if format != OutputFormat::Text {
+        return print_timetable(&data, args, today, config.variant_offset, format);
+    }
+    let current_variant = data.current_variant(today, config.variant_offset);
+    let current = args
+        .highlight
+        .then(|| data.current_period(now, config.variant_offset).ok())
+        .flatten()
+        .map(|period| period.bell);
+    let title = format!("Timetable for {}", data.school.name).bold();
+    if args.today {
+        println!("{title}");
+        println!();
+        println!(
+            "{}",
+            Color::SUBJECTIVE_BLUE
+                .color(today.format("%A, %B %-d, %Y").to_string().as_str())
+                .bold()
+        );
+        let Some(day) = data.get_day(today, config.variant_offset)? else {
+            let reason = data.school.calendar.no_school_reason(today).map_or_else(
+                || format!("No bells on {}", weekday_name(today.weekday())),
+                |reason| reason.to_string(),
+            );
+            println!("{} {}", "No school".yellow(), reason.dimmed());
+            return Ok(());
+        };
+        let rows = Cell::from_day(&data, day)?
+            .into_iter()
+            .map(|cell| vec![Some(cell)])
+            .collect::<Vec<_>>();
+        print_grid(&[weekday_name(today.weekday())], &rows, Some(0), current);
+        return Ok(());
+    }
+    let variants = shown_variants(&data, args, current_variant)?;
+    println!("{title}");
+    for variant in variants {
+        let grid = Grid::new(&data, &data.school.bell_times[variant])?;
+        let this_week = current_variant == Some(variant);
+        println!();
+        println!(
+            "{}{}",
+            Color::SUBJECTIVE_BLUE.color(grid.week.name.as_str()).bold(),
+            if this_week {
+                " (this week)".dimmed()
+            } else {
+                "".normal()
+            }
+        );
+        let headers = grid
+            .weekdays
+            .iter()
+            .map(|weekday| weekday_name(*weekday))
+            .collect::<Vec<_>>();
+        let today = this_week
+            .then(|| {
+                grid.weekdays
+                    .iter()
+                    .position(|weekday| *weekday == today.weekday())
+            })
+            .flatten();
+        print_grid(&headers, &grid.rows, today, current);
+    }
+    Ok(())
+}
 
+async fn export_timetable(
+    config_directory: &Path,
+    format: RenderFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    let data = Subjective::from_config(config_directory)?;
+    let rendered = match format {
+        RenderFormat::Html => to_html(&data)?,
+        RenderFormat::Md => to_markdown(&data)?,
+    };
+    let Some(output) = output else {
+        print!("{rendered}");
+        return Ok(());
+    };
+    write(output, rendered)
+        .await
+        .map_err(|_| anyhow!("Couldn't write timetable to \"{}\".", output.display()))?;
+    println!(
+        "Successfully exported timetable to \"{}\".",
+        output.display()
+    );
+    Ok(())
+}
+
+const fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+fn find_variant(data: &Subjective, name: &str) -> Result<usize> {
+    data.school
+        .bell_times
+        .iter()
+        .position(|week| week.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            anyhow!(
+                "Couldn't find a week variant named \"{name}\". Available variants are: {}.",
+                data.school
+                    .bell_times
+                    .iter()
+                    .map(|week| format!("\"{}\"", week.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+async fn activate(
+    config_directory: &Path,
+    variant: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<()> {
+    if end < start {
+        return Err(anyhow!(
+            "The last date ({end}) must not be before the first date ({start})."
+        ));
+    }
+    let mut data = Subjective::from_config(config_directory)?;
+    let variant = find_variant(&data, variant)?;
+    data.school.variant_activations.push(VariantActivation {
+        variant,
+        start,
+        end,
+    });
+    save(data, config_directory).await
+}
+
+fn activations(config_directory: &Path, format: OutputFormat) -> Result<()> {
+    let data = Subjective::from_config(config_directory)?;
+    if format != OutputFormat::Text {
+        let activations = data
+            .school
+            .variant_activations
+            .iter()
+            .enumerate()
+            .map(|(number, activation)| Activation {
+                number: number + 1,
+                variant: data
+                    .school
+                    .bell_times
+                    .get(activation.variant)
+                    .map(|week| week.name.clone()),
+                from: activation.start,
+                to: activation.end,
+            })
+            .collect::<Vec<_>>();
+        return match format {
+            OutputFormat::Tsv => {
+                print_tsv(
+                    &Activation::HEADER,
+                    &activations.iter().map(Activation::row).collect::<Vec<_>>(),
+                );
+                Ok(())
+            }
+            _ => print_json(&activations),
+        };
+    }
+    if data.school.variant_activations.is_empty() {
+        println!("No week variants are activated.");
+        return Ok(());
+    }
+    for (number, activation) in data.school.variant_activations.iter().enumerate() {
+        let name = data
+            .school
+            .bell_times
+            .get(activation.variant)
+            .map_or("Unknown variant", |week| &week.name);
+        println!(
+            "{} {} {}",
+            format!("{}.", number + 1).dimmed(),
+            Color::SUBJECTIVE_BLUE.color(name),
+            format!("{} to {}", activation.start, activation.end).dimmed()
+        );
+    }
+    Ok(())
+}
+
+async fn deactivate(config_directory: &Path, number: usize) -> Result<()> {
+    let mut data = Subjective::from_config(config_directory)?;
+    if number == 0 || number > data.school.variant_activations.len() {
+        return Err(anyhow!(
+            "There is no activation numbered {number}. Run `sj timetable activations` to see them."
+        ));
+    }
+    data.school.variant_activations.remove(number - 1);
+    save(data, config_directory).await
+}
+
+async fn add_override(
+    config_directory: &Path,
+    override_date: NaiveDate,
+    name: String,
+    weekday: Option<Weekday>,
+    variant: Option<&str>,
+    bells: Option<&Path>,
+) -> Result<()> {
+    let mut data = Subjective::from_config(config_directory)?;
+    let replacement = match (weekday, bells) {
+        (Some(weekday), _) => Replacement::Weekday {
+            variant: variant
+                .map(|variant| find_variant(&data, variant))
+                .transpose()?,
+            weekday,
+        },
+        (None, Some(bells)) => {
+            let json = read_to_string(bells)
+                .await
+                .map_err(|_| anyhow!("Couldn't read bells from \"{}\".", bells.display()))?;
+            let mut bells: Day = serde_json::from_str(&json).map_err(|error| {
+                anyhow!(
+                    "Couldn't parse bells from \"{}\".\n{error}",
+                    bells.display()
+                )
+            })?;
+            bells.sort_by_key(|bell_time| bell_time.time);
+            Replacement::Bells { bells }
+        }
+        (None, None) => return Err(anyhow!("Either a weekday or bells must be given.")),
+    };
+    data.school
+        .overrides
+        .retain(|day_override| day_override.date != override_date);
+    data.school.overrides.push(DayOverride {
+        date: override_date,
+        name,
+        replacement,
+    });
+    data.school
+        .overrides
+        .sort_by_key(|day_override| day_override.date);
+    save(data, config_directory).await
+}
+
+fn list_overrides(config_directory: &Path, format: OutputFormat) -> Result<()> {
+    let data = Subjective::from_config(config_directory)?;
+    if format != OutputFormat::Text {
+        let overrides = data
+            .school
+            .overrides
+            .iter()
+            .map(|day_override| {
+                let (weekday, variant, bells) = match &day_override.replacement {
+                    Replacement::Weekday { variant, weekday } => (
+                        Some(weekday_name(*weekday).to_string()),
+                        variant
+                            .and_then(|variant| data.school.bell_times.get(variant))
+                            .map(|week| week.name.clone()),
+                        None,
+                    ),
+                    Replacement::Bells { bells } => (
+                        None,
+                        None,
+                        Some(
+                            bells
+                                .iter()
+                                .map(|bell| output::Bell::new(&data, bell))
+                                .collect::<Result<_>>()?,
+                        ),
+                    ),
+                };
+                Ok(Override {
+                    date: day_override.date,
+                    name: day_override.name.clone(),
+                    weekday,
+                    variant,
+                    bells,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        return match format {
+            OutputFormat::Tsv => {
+                print_tsv(
+                    &Override::HEADER,
+                    &overrides.iter().map(Override::row).collect::<Vec<_>>(),
+                );
+                Ok(())
+            }
+            _ => print_json(&overrides),
+        };
+    }
+    if data.school.overrides.is_empty() {
+        println!("There are no overrides.");
+        return Ok(());
+    }
+    for DayOverride {
+        date,
+        name,
+        replacement,
+    } in &data.school.overrides
+    {
+        let replacement = match replacement {
+            Replacement::Weekday { variant, weekday } => {
+                let weekday = weekday_name(*weekday);
+                variant
+                    .and_then(|variant| data.school.bell_times.get(variant))
+                    .map_or_else(
+                        || format!("follows {weekday}"),
+                        |week| format!("follows {weekday} of {}", week.name),
+                    )
+            }
+            Replacement::Bells { bells } => format!(
+                "has {} custom bell{}",
+                bells.len(),
+                if bells.len() == 1 { "" } else { "s" }
+            ),
+        };
+        println!(
+            "{} {} {}",
+            date.format("%a %Y-%m-%d").to_string().dimmed(),
+            Color::SUBJECTIVE_BLUE.color(if name.is_empty() { "Override" } else { name }),
+            replacement.dimmed()
+        );
+    }
+    Ok(())
+}
+
+async fn remove_override(config_directory: &Path, override_date: NaiveDate) -> Result<()> {
+    let mut data = Subjective::from_config(config_directory)?;
+    let overrides = data.school.overrides.len();
+    data.school
+        .overrides
+        .retain(|day_override| day_override.date != override_date);
+    if data.school.overrides.len() == overrides {
+        return Err(anyhow!("There are no overrides on {override_date}."));
+    }
+    save(data, config_directory).await
+}
+
+async fn set_timezone(config_directory: &Path, timezone: Option<String>) -> Result<()> {
+    let mut data = Subjective::from_config(config_directory)?;
+    let Some(timezone) = timezone else {
+        match &data.school.timezone {
+            Some(timezone) => println!("{timezone}"),
+            None => println!(
+                "{}",
+                "No timezone is set, so the local timezone is used.".dimmed()
+            ),
+        }
+        return Ok(());
+    };
+    data.school.timezone = Some(timezone);
+    if data.school.tz().is_none() {
+        return Err(anyhow!(
+            "\"{}\" isn't a known timezone, like \"Australia/Sydney\".",
+            data.school.timezone.unwrap_or_default()
+        ));
+    }
+    save(data, config_directory).await
+}
+
+async fn anchor(
+    config_directory: &Path,
+    variant: &str,
+    anchor_date: Option<NaiveDate>,
+    time: Option<Time>,
+) -> Result<()> {
+    let mut data = Subjective::from_config(config_directory)?;
+    let anchor_date = anchor_date.unwrap_or_else(|| school_now(&data, time).date());
+    let variant = find_variant(&data, variant)?;
+    if !data.school.bell_times[variant].cyclical {
+        return Err(anyhow!(
+            "The week variant \"{}\" isn't included in the automatic cycle. Use `sj timetable activate` instead.",
+            data.school.bell_times[variant].name
+        ));
+    }
+    data.school.cycle_anchor = Some(CycleAnchor {
+        date: anchor_date,
+        variant,
+    });
+    save(data, config_directory).await?;
+    let mut config = get_config(config_directory).await?;
+    if config.variant_offset != 0 {
+        config.variant_offset = 0;
+        let config_path = save_config(&config, config_directory).await?;
+        println!(
+            "Reset `variant_offset` in \"{}\", since the cycle is now anchored.",
+            config_path.display()
+        );
+    }
     Ok(())
 }
 
 async fn init_config(config_directory: &Path) -> Result<()> {
+    let config_path = save_config(&Config::default(), config_directory).await?;
+    println!(
+        "Successfully initialised configuration at \"{}\".",
+        config_path.display()
+    );
+    Ok(())
+}
+
+async fn save_config(config: &Config, config_directory: &Path) -> Result<PathBuf> {
     let config_path = config_directory.join("config.toml");
-    let config = Config::default();
     let config =
-        toml::to_string(&config).map_err(|_| anyhow!("Couldn't serialise configuration."))?;
+        toml::to_string(config).map_err(|_| anyhow!("Couldn't serialise configuration."))?;
     create_dir_all(config_directory).await.map_err(|_| {
         anyhow!(
             "Couldn't create configuration directory at \"{}\".",
@@ -192,14 +1256,41 @@ async fn init_config(config_directory: &Path) -> Result<()> {
             config_path.display()
         )
     })?;
+    Ok(config_path)
+}
+
+async fn migrate_config(config_directory: &Path, time: Option<Time>) -> Result<()> {
+    let mut config = get_config(config_directory).await?;
+    let mut data = Subjective::from_config(config_directory)?;
+    let today = school_now(&data, time).date();
+    if config.variant_offset == 0 && data.school.cycle_anchor.is_some() {
+        println!("Configuration is already migrated.");
+        return Ok(());
+    }
+    let activations = take(&mut data.school.variant_activations);
+    let variant = data
+        .current_variant(today, config.variant_offset)
+        .ok_or_else(|| anyhow!("Couldn't migrate, because no week variants are cyclical."))?;
+    data.school.variant_activations = activations;
+    data.school.cycle_anchor = Some(CycleAnchor {
+        date: today,
+        variant,
+    });
     println!(
-        "Successfully initialised configuration at \"{}\".",
+        "Anchored the week of {today} to \"{}\".",
+        data.school.bell_times[variant].name
+    );
+    save(data, config_directory).await?;
+    config.variant_offset = 0;
+    let config_path = save_config(&config, config_directory).await?;
+    println!(
+        "Successfully migrated configuration at \"{}\".",
         config_path.display()
     );
     Ok(())
 }
 
-async fn pull(server: &String, config_directory: &Path) -> Result<()> {
+async fn pull(server: &String, config_directory: &Path, normalize: bool) -> Result<()> {
     info!("Fetching schools from \"{server}\"...");
     let response = get(format!("{server}/schools.json")).await.map_err(|_| {
         anyhow!(formatdoc!(
@@ -213,8 +1304,9 @@ async fn pull(server: &String, config_directory: &Path) -> Result<()> {
         .await
         .map_err(|_| anyhow!("Couldn't get text from response."))?;
     info!("Parsing data...");
-    let schools: Vec<School> = serde_json::from_str(&text)
+    let (schools, migrations) = School::catalog_from_json(&text)
         .map_err(|error| anyhow!("Couldn't parse schools from text.\n{error}"))?;
+    print_migrations(&migrations);
     info!("Prompting user for school...");
     let school = loop {
         let school = Select::new("Choose a school", schools.clone())
@@ -228,10 +1320,32 @@ async fn pull(server: &String, config_directory: &Path) -> Result<()> {
             Err(_) => {},
         }
     };
-    save(Subjective::from_school(school), config_directory).await
+    let mut data = Subjective::from_school(school);
+    if normalize {
+        print_changes(&data.normalize());
+    }
+    save(data, config_directory).await
+}
+
+fn print_migrations(migrations: &[Migration]) {
+    for migration in migrations {
+        println!("{}", migration.to_string().dimmed());
+    }
 }
 
-async fn save(data: Subjective, config_directory: &Path) -> Result<()> {
+fn print_changes(changes: &[Change]) {
+    for change in changes {
+        println!("{}", change.to_string().dimmed());
+    }
+    println!(
+        "Normalised data with {} change{}.",
+        changes.len(),
+        if changes.len() == 1 { "" } else { "s" }
+    );
+}
+
+async fn save(mut data: Subjective, config_directory: &Path) -> Result<()> {
+    data.school.assign_ids();
     info!("Serialising to JSON...");
     let json =
         serde_json::to_string(&data).map_err(|_| anyhow!("Couldn't serialise data to JSON."))?;
@@ -251,19 +1365,256 @@ async fn save(data: Subjective, config_directory: &Path) -> Result<()> {
     Ok(())
 }
 
-async fn load(file: &str, config_directory: &Path) -> Result<()> {
-    info!("Reading data from \"{file}\"...");
-    let json = read_to_string(file)
+async fn export(
+    config_directory: &Path,
+    format: ExportFormat,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    output: Option<&Path>,
+    time: Option<Time>,
+) -> Result<()> {
+    let data = Subjective::from_config(config_directory)?;
+    let exported = match format {
+        ExportFormat::Ics => {
+            let config = get_config(config_directory).await?;
+            let from = from.unwrap_or_else(|| school_now(&data, time).date());
+            let to = to.ok_or_else(|| {
+                anyhow!("Exporting to iCalendar needs the last date to export, given with --to.")
+            })?;
+            to_ical(&data, from, to, config.variant_offset, Utc::now())?.into_bytes()
+        }
+        ExportFormat::Csv => return export_csv(&data, output).await,
+        ExportFormat::Binary => to_binary(&data)
+            .map_err(|error| anyhow!("Couldn't write binary timetable.\n{error}"))?,
+    };
+    let Some(output) = output else {
+        stdout()
+            .write_all(&exported)
+            .map_err(|_| anyhow!("Couldn't write data to standard output."))?;
+        return Ok(());
+    };
+    write(output, exported)
         .await
-        .map_err(|_| anyhow!("Couldn't read data from \"{file}\"."))?;
-    info!("Parsing data...");
-    let data: Subjective = serde_json::from_str(&json)
-        .map_err(|error| anyhow!("Couldn't parse data from \"{file}\".\n{error}"))?;
+        .map_err(|_| anyhow!("Couldn't write data to \"{}\".", output.display()))?;
+    println!("Successfully exported data to \"{}\".", output.display());
+    Ok(())
+}
+
+async fn schema(catalog: bool, output: Option<&Path>) -> Result<()> {
+    let schema = if catalog {
+        catalog_schema()
+    } else {
+        data_schema()
+    };
+    let json = serde_json::to_string_pretty(&schema)
+        .map_err(|_| anyhow!("Couldn't serialise schema to JSON."))?;
+    let Some(output) = output else {
+        println!("{json}");
+        return Ok(());
+    };
+    write(output, json)
+        .await
+        .map_err(|_| anyhow!("Couldn't write schema to \"{}\".", output.display()))?;
+    println!("Successfully wrote schema to \"{}\".", output.display());
+    Ok(())
+}
+
+async fn export_csv(data: &Subjective, output: Option<&Path>) -> Result<()> {
+    let directory = output.ok_or_else(|| {
+        anyhow!("Exporting to CSV needs a directory to write to, given with --out.")
+    })?;
+    let mut bells = Vec::new();
+    let mut subjects = Vec::new();
+    to_csv(data, &mut bells, &mut subjects)?;
+    create_dir_all(directory)
+        .await
+        .map_err(|_| anyhow!("Couldn't create directory at \"{}\".", directory.display()))?;
+    for (name, sheet) in [(CSV_BELLS_FILE, bells), (CSV_SUBJECTS_FILE, subjects)] {
+        let path = directory.join(name);
+        write(&path, sheet)
+            .await
+            .map_err(|_| anyhow!("Couldn't write data to \"{}\".", path.display()))?;
+    }
+    println!("Successfully exported data to \"{}\".", directory.display());
+    Ok(())
+}
+
+async fn migrate(config_directory: &Path) -> Result<()> {
+    let file_path = config_directory.join(Subjective::CONFIG_FILE);
+    info!("Reading data from \"{}\"...", file_path.display());
+    let text = read_to_string(&file_path)
+        .await
+        .map_err(|_| anyhow!("Couldn't read data from \"{}\".", file_path.display()))?;
+    let (data, migrations) = Subjective::from_json(&text)?;
+    if migrations.is_empty() {
+        println!("Data is already at version {CURRENT_VERSION}.");
+        return Ok(());
+    }
+    print_migrations(&migrations);
+    save(data, config_directory).await
+}
+
+fn check(config_directory: &Path, format: OutputFormat) -> Result<()> {
+    let data = Subjective::from_config(config_directory)?;
+    let diagnostics = data.validate();
+    match format {
+        OutputFormat::Text => report(&diagnostics),
+        OutputFormat::Json | OutputFormat::Tsv => {
+            let problems = diagnostics.iter().map(Problem::new).collect::<Vec<_>>();
+            if format == OutputFormat::Json {
+                print_json(&problems)?;
+            } else {
+                print_tsv(
+                    &Problem::HEADER,
+                    &problems.iter().map(Problem::row).collect::<Vec<_>>(),
+                );
+            }
+            count_problems(&diagnostics)
+        }
+    }
+}
+
+fn report(diagnostics: &[Diagnostic]) -> Result<()> {
+    for diagnostic in diagnostics {
+        let severity = match diagnostic.severity() {
+            Severity::Warning => diagnostic.severity().to_string().yellow(),
+            Severity::Error => diagnostic.severity().to_string().red(),
+        };
+        println!("{severity}: {diagnostic}");
+    }
+    count_problems(diagnostics)?;
+    let warnings = diagnostics.len();
+    if warnings > 0 {
+        println!(
+            "Found {warnings} warning{} in your Subjective data.",
+            if warnings == 1 { "" } else { "s" }
+        );
+    } else {
+        println!("No problems found in your Subjective data.");
+    }
+    Ok(())
+}
+
+/// Fail if any diagnostics are errors, with the number of errors and warnings.
+fn count_problems(diagnostics: &[Diagnostic]) -> Result<()> {
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity() == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    if errors > 0 {
+        return Err(anyhow!(
+            "Found {errors} error{} and {warnings} warning{} in your Subjective data.",
+            if errors == 1 { "" } else { "s" },
+            if warnings == 1 { "" } else { "s" }
+        ));
+    }
+    Ok(())
+}
+
+async fn build(file: &str, output: Option<&Path>, config_directory: &Path) -> Result<()> {
+    info!("Reading source from \"{file}\"...");
+    let source = read_to_string(file)
+        .await
+        .map_err(|_| anyhow!("Couldn't read source from \"{file}\"."))?;
+    info!("Compiling source...");
+    let data = compile(&source)
+        .map_err(|error| anyhow!("Couldn't build data from \"{file}\".\n{error}"))?;
+    report(&data.validate())?;
+    let Some(output) = output else {
+        return save(data, config_directory).await;
+    };
+    let json =
+        serde_json::to_string(&data).map_err(|_| anyhow!("Couldn't serialise data to JSON."))?;
+    write(output, json)
+        .await
+        .map_err(|_| anyhow!("Couldn't write data to \"{}\".", output.display()))?;
+    println!("Successfully built data to \"{}\".", output.display());
+    Ok(())
+}
+
+fn import_format(file: &Path) -> ImportFormat {
+    if file.is_dir() {
+        return ImportFormat::Csv;
+    }
+    match file
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("ics") => ImportFormat::Ics,
+        Some("csv") => ImportFormat::Csv,
+        _ => ImportFormat::Json,
+    }
+}
+
+async fn read_sheet(directory: &Path, name: &str) -> Result<String> {
+    let path = directory.join(name);
+    read_to_string(&path)
+        .await
+        .map_err(|_| anyhow!("Couldn't read data from \"{}\".", path.display()))
+}
+
+async fn load_csv(file: &str, config_directory: &Path) -> Result<Subjective> {
+    let path = Path::new(file);
+    let directory = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+    let bells = read_sheet(directory, CSV_BELLS_FILE).await?;
+    let subjects = read_sheet(directory, CSV_SUBJECTS_FILE).await?;
+    let data = match Subjective::from_config(config_directory) {
+        Ok(data) => data,
+        Err(LoadDataError::DataFileNotFound(..)) => {
+            Subjective::new(School::new("Imported Timetable"), Vec::new())
+        }
+        Err(error) => return Err(error.into()),
+    };
+    info!("Importing sheets...");
+    from_csv(data, bells.as_bytes(), subjects.as_bytes()).map_err(|error| {
+        anyhow!(
+            "Couldn't import data from \"{}\".\n{error}",
+            directory.display()
+        )
+    })
+}
+
+async fn load(
+    file: &str,
+    format: Option<ImportFormat>,
+    config_directory: &Path,
+    normalize: bool,
+) -> Result<()> {
+    info!("Reading data from \"{file}\"...");
+    let format = format.unwrap_or_else(|| import_format(Path::new(file)));
+    let mut data: Subjective = if format == ImportFormat::Csv {
+        load_csv(file, config_directory).await?
+    } else {
+        let text = read_to_string(file)
+            .await
+            .map_err(|_| anyhow!("Couldn't read data from \"{file}\"."))?;
+        if format == ImportFormat::Ics {
+            info!("Importing calendar...");
+            from_ical(&text)
+                .map_err(|error| anyhow!("Couldn't import data from \"{file}\".\n{error}"))?
+        } else {
+            info!("Parsing data...");
+            let (data, migrations) = Subjective::from_json(&text)
+                .map_err(|error| anyhow!("Couldn't parse data from \"{file}\".\n{error}"))?;
+            print_migrations(&migrations);
+            data
+        }
+    };
+    if normalize {
+        print_changes(&data.normalize());
+    }
     save(data, config_directory).await
 }
 
 #[derive(Deserialize, Serialize)]
 struct Config {
+    #[serde(default)]
     variant_offset: usize,
 }
 
@@ -290,8 +1641,78 @@ async fn get_config(config_directory: &Path) -> Result<Config> {
     })
 }
 
+const PROGRESS_WIDTH: usize = 20;
+
+fn format_progress(period: &Period, time: NaiveTime) -> Result<String> {
+    let to_std = |duration: TimeDelta| {
+        duration.to_std().map_err(|_| {
+            anyhow!("Couldn't convert time to standard library `std::time::Duration`.")
+        })
+    };
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let filled = (period.progress(time) * PROGRESS_WIDTH as f64).round() as usize;
+    Ok(format!(
+        "{}{} {} {}, {} {}",
+        "█".repeat(filled).green(),
+        "░".repeat(PROGRESS_WIDTH - filled).dimmed(),
+        format_duration(to_std(period.elapsed(time))?)
+            .to_string()
+            .yellow(),
+        "elapsed".dimmed(),
+        format_duration(to_std(period.remaining(time))?)
+            .to_string()
+            .yellow(),
+        "remaining".dimmed()
+    ))
+}
+
+fn now_report(data: &Subjective, now: NaiveDateTime, variant_offset: usize) -> Result<Now> {
+    let today = now.date();
+    let (status, reason) = match (
+        data.get_override(today),
+        data.school.calendar.no_school_reason(today),
+    ) {
+        (Some(day_override), _) => (DayStatus::Altered, Some(day_override.name.clone())),
+        (None, Some(reason)) => (DayStatus::NoSchool, Some(reason.to_string())),
+        (None, None) if matches!(data.get_day(today, variant_offset), Ok(None)) => (
+            DayStatus::NoSchool,
+            Some(format!("No bells on {}", weekday_name(today.weekday()))),
+        ),
+        (None, None) => (DayStatus::School, None),
+    };
+    let current = data
+        .bells_before(now, variant_offset)
+        .next()
+        .filter(|(date, _)| *date == today)
+        .map(|(date, bell_time)| output::Bell::on_date(data, bell_time, date, variant_offset))
+        .transpose()?;
+    let mut upcoming = data.bells_from(now, variant_offset).peekable();
+    let next = match upcoming.peek() {
+        Some(&(next_date, _)) => upcoming
+            .take_while(|(date, _)| *date == next_date)
+            .map(|(date, bell_time)| output::Bell::on_date(data, bell_time, date, variant_offset))
+            .collect::<Result<_>>()?,
+        None => Vec::new(),
+    };
+    Ok(Now {
+        time: timestamp(data, now),
+        today: output::Day { status, reason },
+        current,
+        period: data
+            .current_period(now, variant_offset)
+            .ok()
+            .map(|period| CurrentPeriod::new(data, &period, now)),
+        next,
+    })
+}
+
+/// Get the text shown by `sj now`, at a time.
 #[allow(clippy::too_many_lines)]
-async fn now(config_directory: &Path, now: DateTime<Local>) -> Result<()> {
+fn now_text(data: &Subjective, now: NaiveDateTime, variant_offset: usize) -> Result<String> {
     fn format(
         bell_time: &BellTime,
         output: &mut String,
@@ -309,89 +1730,190 @@ async fn now(config_directory: &Path, now: DateTime<Local>) -> Result<()> {
         )
         .map_err(|error| anyhow!(error))
     }
-    let config = get_config(config_directory).await?;
-    let data = Subjective::from_config(config_directory)?;
     let time_now = now.time().format("%-I:%M %p").to_string().dimmed();
-    let date_now = now
-        .date_naive()
-        .format("%A, %B %-d, %Y")
-        .to_string()
-        .dimmed();
+    let date_now = now.date().format("%A, %B %-d, %Y").to_string().dimmed();
+    let today = now.date();
     let last = data
-        .find_first_before(now.naive_local(), config.variant_offset)
-        .ok();
-    let next = data
-        .find_first_after(now.naive_local(), config.variant_offset)
-        .ok();
+        .bells_before(now, variant_offset)
+        .next()
+        .filter(|(date, _)| *date == today);
+    let mut upcoming = data.bells_from(now, variant_offset);
+    let next = upcoming.next();
 
     let mut output = String::new();
     writeln!(output, "{} {time_now} {date_now}", "Now".green())?;
-    if let Some(bell_time) = last {
-        format(bell_time, &mut output, true, &data)?;
-    }
-    if let Some(bell_time) = next {
+    if let Some(day_override) = data.get_override(today) {
         writeln!(
             output,
-            "{} {} {}",
-            "Upcoming".green(),
-            bell_time.time.format("%-I:%M %p").to_string().dimmed(),
-            format_duration(
-                (now.time() - bell_time.time)
-                    .abs()
-                    .to_std()
-                    .map_err(|_| anyhow!(
-                        "Couldn't convert time to standard library `std::time::Duration`."
-                    ))?
-            )
-            .to_string()
-            .yellow()
+            "    {} {}",
+            "Altered day".yellow(),
+            day_override.name.dimmed()
         )?;
-        format(bell_time, &mut output, false, &data)?;
-        let next = data
-            .find_all_after(now.naive_local(), config.variant_offset)
-            .unwrap_or_default();
-        if next.len() > 1 {
-            writeln!(output, "{}", "Next".green())?;
-            for bell_time in next.iter().skip(1) {
-                format(bell_time, &mut output, true, &data)?;
+    } else if let Some(reason) = data.school.calendar.no_school_reason(today) {
+        writeln!(
+            output,
+            "    {} {}",
+            "No school".yellow(),
+            reason.to_string().dimmed()
+        )?;
+    } else if matches!(data.get_day(today, variant_offset), Ok(None)) {
+        writeln!(
+            output,
+            "    {} {}",
+            "No school".yellow(),
+            format!("No bells on {}", weekday_name(today.weekday())).dimmed()
+        )?;
+    }
+    if let Some((_, bell_time)) = last {
+        format(bell_time, &mut output, true, data)?;
+    }
+    if let Ok(period) = data.current_period(now, variant_offset) {
+        writeln!(output, "    {}", format_progress(&period, now.time())?)?;
+    }
+    if let Some((date, bell_time)) = next {
+        if date == today {
+            writeln!(
+                output,
+                "{} {} {}",
+                "Upcoming".green(),
+                bell_time.time.format("%-I:%M %p").to_string().dimmed(),
+                format_duration(
+                    data.duration_between(now, date.and_time(bell_time.time))
+                        .abs()
+                        .to_std()
+                        .map_err(|_| anyhow!(
+                            "Couldn't convert time to standard library `std::time::Duration`."
+                        ))?
+                )
+                .to_string()
+                .yellow()
+            )?;
+            format(bell_time, &mut output, false, data)?;
+            let mut next = upcoming
+                .take_while(|(next_date, _)| *next_date == date)
+                .peekable();
+            if next.peek().is_some() {
+                writeln!(output, "{}", "Next".green())?;
+                for (_, bell_time) in next {
+                    format(bell_time, &mut output, true, data)?;
+                }
             }
-        }
-    } else {
-        let current_variant = get_current_variant(
-            now.date_naive(),
-            config.variant_offset,
-            data.school.bell_times.len(),
-        );
-        let next_day_with_bells = repeat(data.school.bell_times.iter())
-            .flatten()
-            .skip(current_variant)
-            .flat_map(|Week { days, .. }| {
-                days.iter()
-                    .zip((0..).map_while(|days| Weekday::try_from(days).ok()))
-            })
-            .skip(now.weekday().num_days_from_sunday() as usize)
-            .find(|(day, _)| !day.is_empty());
-        if let Some((day, weekday)) = next_day_with_bells {
-            const WEEKDAYS: [&str; 7] = [
-                "Monday",
-                "Tuesday",
-                "Wednesday",
-                "Thursday",
-                "Friday",
-                "Saturday",
-                "Sunday",
-            ];
+        } else {
             writeln!(
                 output,
                 "{} {}",
                 "Upcoming".green(),
-                WEEKDAYS[weekday.num_days_from_monday() as usize]
+                date.format("%A, %B %-d")
             )?;
-            for bell_time in day {
-                format(bell_time, &mut output, true, &data)?;
+            format(bell_time, &mut output, true, data)?;
+            for (_, bell_time) in upcoming.take_while(|(next_date, _)| *next_date == date) {
+                format(bell_time, &mut output, true, data)?;
             }
         }
     }
-    print!("{output}");
+    Ok(output)
+}
+
+async fn now(
+    config_directory: &Path,
+    time: Option<Time>,
+    output_format: OutputFormat,
+    watch: bool,
+) -> Result<()> {
+    if watch {
+        return watch_now(config_directory, time).await;
+    }
+    let config = get_config(config_directory).await?;
+    let data = Subjective::from_config(config_directory)?;
+    let now = school_now(&data, time);
+    match output_format {
+        OutputFormat::Text => print!("{}", now_text(&data, now, config.variant_offset)?),
+        OutputFormat::Json => print_json(&now_report(&data, now, config.variant_offset)?)?,
+        OutputFormat::Tsv => print_tsv(
+            &Now::header(),
+            &now_report(&data, now, config.variant_offset)?.rows(),
+        ),
+    }
+    Ok(())
+}
+
+async fn tui(config_directory: &Path, time: Option<Time>) -> Result<()> {
+    let config = get_config(config_directory).await?;
+    let data = Subjective::from_config(config_directory)?;
+    let start = Instant::now();
+    tui::run(&data, config.variant_offset, |data| {
+        running_now(data, time, start)
+    })
+}
+
+async fn bar(
+    config_directory: &Path,
+    time: Option<Time>,
+    format: BarFormat,
+    continuous: bool,
+) -> Result<()> {
+    let config = get_config(config_directory).await?;
+    let data = Subjective::from_config(config_directory)?;
+    let start = Instant::now();
+    bar::run(&data, config.variant_offset, format, continuous, |data| {
+        running_now(data, time, start)
+    })
+    .await
+}
+
+/// Get the times the data and configuration files were last modified, to notice changes.
+fn modified_times(config_directory: &Path) -> [Option<SystemTime>; 2] {
+    [Subjective::CONFIG_FILE, "config.toml"].map(|file| {
+        metadata(config_directory.join(file))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    })
+}
+
+/// Redraw `sj now` in place every second, until Ctrl-C is pressed. The data and configuration
+/// are read again when they change, and errors reading them or finding bells are shown in place
+/// of the bells. A time given with `--time` advances as time passes.
+async fn watch_now(config_directory: &Path, time: Option<Time>) -> Result<()> {
+    let mut config = get_config(config_directory).await?;
+    let mut data = Subjective::from_config(config_directory)?;
+    let mut modified = modified_times(config_directory);
+    let mut error = None;
+    let start = Instant::now();
+    let mut interval = interval(Duration::from_secs(1));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut stdout = stdout();
+    // Listen for Ctrl-C once, so that it isn't missed while redrawing or reloading.
+    let mut interrupt = pin!(ctrl_c());
+    write!(stdout, "\x1b[?25l\x1b[2J")?;
+    loop {
+        select! {
+            _ = &mut interrupt => break,
+            _ = interval.tick() => {}
+        }
+        if modified_times(config_directory) != modified {
+            modified = modified_times(config_directory);
+            error = match (
+                get_config(config_directory).await,
+                Subjective::from_config(config_directory),
+            ) {
+                (Ok(new_config), Ok(new_data)) => {
+                    config = new_config;
+                    data = new_data;
+                    None
+                }
+                (Err(new_error), _) => Some(new_error),
+                (_, Err(new_error)) => Some(new_error.into()),
+            };
+        }
+        let now = running_now(&data, time, start);
+        let mut text = now_text(&data, now, config.variant_offset)
+            .unwrap_or_else(|error| format!("{}\n", error.to_string().red()));
+        if let Some(error) = &error {
+            writeln!(text, "{}", format!("Couldn't reload data. {error}").red())?;
+        }
+        write!(stdout, "\x1b[H{}\x1b[J", text.replace('\n', "\x1b[K\n"))?;
+        stdout.flush()?;
+    }
+    writeln!(stdout, "\x1b[?25h")?;
     Ok(())
 }
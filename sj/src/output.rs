@@ -0,0 +1,398 @@
+//! Machine-readable output of sj commands, selected with `--output`.
+//!
+//! The JSON and TSV formats are stable: fields and columns are only ever added, never renamed or
+//! removed. Times of day are in the school's local time, formatted like `08:40:00`, and instants
+//! are ISO 8601 timestamps, like `2026-02-10T08:40:00+11:00`, which have no offset if the school
+//! has no timezone.
+
+use std::{ptr, slice};
+
+use anyhow::{Result, anyhow};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use clap::ValueEnum;
+use serde::Serialize;
+use subjective::{
+    Subjective,
+    render::Cell,
+    school::{bells::BellTime, period::Period},
+    validation::Diagnostic,
+};
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    #[value(help = "Colored text, for people")]
+    Text,
+    #[value(help = "JSON, for jq and other tools")]
+    Json,
+    #[value(help = "Tab-separated values with a header row, for shell scripts")]
+    Tsv,
+}
+
+/// Get an instant as an ISO 8601 timestamp, with the school's offset if it has a timezone.
+pub fn timestamp(data: &Subjective, date_time: NaiveDateTime) -> String {
+    data.localize(date_time).map_or_else(
+        || date_time.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        |date_time| date_time.to_rfc3339(),
+    )
+}
+
+/// Get a time of day, like `08:40:00`.
+fn time_of_day(time: NaiveTime) -> String {
+    time.format("%H:%M:%S").to_string()
+}
+
+#[derive(Serialize, Debug)]
+pub struct Subject {
+    /// UUID of the subject.
+    pub id: String,
+    /// Name of the subject.
+    pub name: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Bell {
+    /// Time the bell rings, like `08:40:00`.
+    pub time: String,
+    /// Instant the bell rings, for bells on a date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at: Option<String>,
+    /// Time of the next enabled bell, which ends the period started by an enabled bell.
+    pub end: Option<String>,
+    /// Name of the bell, like `Period 1`.
+    pub name: String,
+    /// Type of the bell, like `Class`, `Break`, or `Time`.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// Whether the bell is enabled.
+    pub enabled: bool,
+    /// Subject of a class.
+    pub subject: Option<Subject>,
+    /// Location of a class.
+    pub location: Option<String>,
+}
+
+impl Bell {
+    /// Resolve a bell which rings on a date, with its subject and location, and the end of its
+    /// period.
+    pub fn on_date(
+        data: &Subjective,
+        bell: &BellTime,
+        on: NaiveDate,
+        variant_offset: usize,
+    ) -> Result<Self> {
+        let day = data
+            .get_day(on, variant_offset)?
+            .filter(|day| day.iter().any(|other| ptr::eq(other, bell)))
+            .map_or_else(|| slice::from_ref(bell), Vec::as_slice);
+        Cell::from_day(data, day)?
+            .iter()
+            .find(|cell| ptr::eq(cell.bell, bell))
+            .map(|cell| Self::from_cell(data, cell, Some(on)))
+            .ok_or_else(|| anyhow!("Couldn't resolve bell \"{}\".", bell.name))
+    }
+
+    /// Resolve a bell, with its subject and location.
+    pub fn new(data: &Subjective, bell: &BellTime) -> Result<Self> {
+        Cell::from_day(data, slice::from_ref(bell))?
+            .first()
+            .map(|cell| Self::from_cell(data, cell, None))
+            .ok_or_else(|| anyhow!("Couldn't resolve bell \"{}\".", bell.name))
+    }
+
+    /// Resolve the bell of a cell of a timetable grid, on a date if it has one.
+    pub fn from_cell(data: &Subjective, cell: &Cell, on: Option<NaiveDate>) -> Self {
+        Self {
+            time: time_of_day(cell.bell.time),
+            at: on.map(|on| timestamp(data, on.and_time(cell.bell.time))),
+            end: cell.end.map(time_of_day),
+            name: cell.bell.name.clone(),
+            kind: cell.bell.bell_data.as_ref().map(ToString::to_string),
+            enabled: cell.bell.enabled,
+            subject: cell.subject.map(|subject| Subject {
+                id: subject.id.to_string(),
+                name: subject.name.clone(),
+            }),
+            location: cell.subject.map(|_| cell.location.to_string()),
+        }
+    }
+
+    /// Get the bell as TSV columns, after the given leading columns.
+    fn columns(&self, leading: &[&str]) -> Vec<String> {
+        leading
+            .iter()
+            .map(ToString::to_string)
+            .chain([
+                self.time.clone(),
+                self.end.clone().unwrap_or_default(),
+                self.name.clone(),
+                self.kind.clone().unwrap_or_default(),
+                self.enabled.to_string(),
+                self.subject
+                    .as_ref()
+                    .map(|subject| subject.name.clone())
+                    .unwrap_or_default(),
+                self.location.clone().unwrap_or_default(),
+            ])
+            .collect()
+    }
+}
+
+/// TSV columns of a [`Bell`], after any leading columns.
+const BELL_COLUMNS: [&str; 7] = [
+    "time", "end", "name", "type", "enabled", "subject", "location",
+];
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DayStatus {
+    /// The day has bells.
+    School,
+    /// The day's bells are replaced by an override.
+    Altered,
+    /// There is no school.
+    NoSchool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Day {
+    /// Whether there is school.
+    pub status: DayStatus,
+    /// Name of the override, or why there is no school.
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CurrentPeriod {
+    /// Instant the period started.
+    pub start: String,
+    /// Instant the period ends.
+    pub end: String,
+    /// Seconds elapsed since the start of the period.
+    pub elapsed: i64,
+    /// Seconds remaining until the end of the period.
+    pub remaining: i64,
+    /// Fraction of the period which has elapsed, from 0 to 1.
+    pub progress: f64,
+}
+
+impl CurrentPeriod {
+    pub fn new(data: &Subjective, period: &Period, now: NaiveDateTime) -> Self {
+        let time = now.time();
+        Self {
+            start: timestamp(data, now.date().and_time(period.start)),
+            end: timestamp(data, now.date().and_time(period.end)),
+            elapsed: period.elapsed(time).num_seconds(),
+            remaining: period.remaining(time).num_seconds(),
+            progress: period.progress(time),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Now {
+    /// The current instant.
+    pub time: String,
+    /// Whether there is school today.
+    pub today: Day,
+    /// The last bell which rang today.
+    pub current: Option<Bell>,
+    /// The period in progress.
+    pub period: Option<CurrentPeriod>,
+    /// The next bells, which are the rest of today's bells, or the bells of the next day with
+    /// school.
+    pub next: Vec<Bell>,
+}
+
+impl Now {
+    /// Get the current and next bells as TSV rows, with the role of each bell.
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        self.current
+            .iter()
+            .map(|bell| bell.columns(&["current", bell.at.as_deref().unwrap_or_default()]))
+            .chain(
+                self.next
+                    .iter()
+                    .map(|bell| bell.columns(&["next", bell.at.as_deref().unwrap_or_default()])),
+            )
+            .collect()
+    }
+
+    /// TSV columns of [`Self::rows`].
+    pub fn header() -> Vec<&'static str> {
+        ["role", "at"].into_iter().chain(BELL_COLUMNS).collect()
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct TimetableDay {
+    /// Weekday, like `Monday`.
+    pub weekday: String,
+    /// Date, for bells on a date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    /// Bells of the day, in order.
+    pub bells: Vec<Bell>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TimetableWeek {
+    /// Name of the week variant.
+    pub name: String,
+    /// Whether the week variant is active this week.
+    pub this_week: bool,
+    /// Days of the week, which are Monday to Friday, and the weekend if it has bells.
+    pub days: Vec<TimetableDay>,
+}
+
+impl TimetableWeek {
+    /// Get the bells as TSV rows, with the week variant and weekday of each bell.
+    pub fn rows(weeks: &[Self]) -> Vec<Vec<String>> {
+        weeks
+            .iter()
+            .flat_map(|week| {
+                week.days.iter().flat_map(|day| {
+                    day.bells
+                        .iter()
+                        .map(|bell| bell.columns(&[&week.name, &day.weekday]))
+                })
+            })
+            .collect()
+    }
+
+    /// TSV columns of [`Self::rows`].
+    pub fn header() -> Vec<&'static str> {
+        ["week", "weekday"]
+            .into_iter()
+            .chain(BELL_COLUMNS)
+            .collect()
+    }
+}
+
+impl TimetableDay {
+    /// Get the bells as TSV rows, with the date of each bell.
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        let date = self.date.map(|date| date.to_string()).unwrap_or_default();
+        self.bells
+            .iter()
+            .map(|bell| bell.columns(&[&date, bell.at.as_deref().unwrap_or_default()]))
+            .collect()
+    }
+
+    /// TSV columns of [`Self::rows`].
+    pub fn header() -> Vec<&'static str> {
+        ["date", "at"].into_iter().chain(BELL_COLUMNS).collect()
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Activation {
+    /// Number of the activation, which is used to remove it.
+    pub number: usize,
+    /// Name of the activated week variant.
+    pub variant: Option<String>,
+    /// First date the variant is active on.
+    pub from: NaiveDate,
+    /// Last date the variant is active on, inclusive.
+    pub to: NaiveDate,
+}
+
+impl Activation {
+    /// TSV columns of [`Self::row`].
+    pub const HEADER: [&str; 4] = ["number", "variant", "from", "to"];
+
+    /// Get the activation as a TSV row.
+    pub fn row(&self) -> Vec<String> {
+        vec![
+            self.number.to_string(),
+            self.variant.clone().unwrap_or_default(),
+            self.from.to_string(),
+            self.to.to_string(),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Override {
+    /// Date which is overridden.
+    pub date: NaiveDate,
+    /// Description of the override.
+    pub name: String,
+    /// Weekday whose bells are followed, like `Monday`.
+    pub weekday: Option<String>,
+    /// Name of the week variant whose bells are followed, or `null` for the active variant.
+    pub variant: Option<String>,
+    /// Custom bells, instead of following a weekday.
+    pub bells: Option<Vec<Bell>>,
+}
+
+impl Override {
+    /// TSV columns of [`Self::row`].
+    pub const HEADER: [&str; 5] = ["date", "name", "weekday", "variant", "bells"];
+
+    /// Get the override as a TSV row.
+    pub fn row(&self) -> Vec<String> {
+        vec![
+            self.date.to_string(),
+            self.name.clone(),
+            self.weekday.clone().unwrap_or_default(),
+            self.variant.clone().unwrap_or_default(),
+            self.bells
+                .as_ref()
+                .map(|bells| bells.len().to_string())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Problem {
+    /// Severity of the problem, `warning` or `error`.
+    pub severity: String,
+    /// Where the problem was found, like `Week "Week A", Mon`.
+    pub location: String,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Problem {
+    /// TSV columns of [`Self::row`].
+    pub const HEADER: [&str; 3] = ["severity", "location", "message"];
+
+    pub fn new(diagnostic: &Diagnostic) -> Self {
+        Self {
+            severity: diagnostic.severity().to_string(),
+            location: diagnostic.location.clone(),
+            message: diagnostic.issue.to_string(),
+        }
+    }
+
+    /// Get the problem as a TSV row.
+    pub fn row(&self) -> Vec<String> {
+        vec![
+            self.severity.clone(),
+            self.location.clone(),
+            self.message.clone(),
+        ]
+    }
+}
+
+/// Print a value as pretty JSON.
+pub fn print_json(value: &impl Serialize) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|_| anyhow!("Couldn't serialise output to JSON."))?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Print rows as TSV, with a header row. Tabs and line breaks in fields are replaced by spaces.
+pub fn print_tsv<S: AsRef<str>>(header: &[&str], rows: &[Vec<S>]) {
+    println!("{}", header.join("\t"));
+    for row in rows {
+        let row = row
+            .iter()
+            .map(|field| field.as_ref().replace(['\t', '\n', '\r'], " "))
+            .collect::<Vec<_>>();
+        println!("{}", row.join("\t"));
+    }
+}
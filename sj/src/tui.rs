@@ -0,0 +1,445 @@
+//! Full-screen terminal dashboard, shown by `sj tui`.
+
+use std::time::Duration;
+
+use crate::weekday_name;
+use anyhow::Result;
+use chrono::{Datelike, NaiveDateTime, TimeDelta, Weekday};
+use colored::control;
+use humantime::format_duration;
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Cell as TableCell, LineGauge, List, Paragraph, Row, Table, TableState, Wrap},
+};
+use subjective::{
+    Subjective,
+    render::{Cell, Grid},
+    school::bells::BellTime,
+};
+
+/// How often the dashboard is redrawn when no key is pressed.
+const TICK: Duration = Duration::from_millis(250);
+
+/// Apply the parameters of an ANSI SGR escape code, like `1;38;2;255;0;0`, to a style.
+fn apply_sgr(mut style: Style, parameters: &str) -> Style {
+    let mut codes = parameters
+        .split(';')
+        .map(|code| code.parse::<u8>().unwrap_or_default());
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(Color::Indexed(code - 30)),
+            90..=97 => style.fg(Color::Indexed(code - 90 + 8)),
+            38 => match codes.next() {
+                Some(2) => {
+                    let [red, green, blue] =
+                        [codes.next(), codes.next(), codes.next()].map(Option::unwrap_or_default);
+                    style.fg(Color::Rgb(red, green, blue))
+                }
+                Some(5) => style.fg(Color::Indexed(codes.next().unwrap_or_default())),
+                _ => style,
+            },
+            39 => style.fg(Color::Reset),
+            _ => style,
+        };
+    }
+    style
+}
+
+/// Convert text colored with ANSI escape codes, like the output of [`BellTime::format`], to a
+/// line.
+fn ansi_line(text: &str) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        let Some(end) = rest[start..].find('m') else {
+            break;
+        };
+        if start > 0 {
+            spans.push(Span::styled(rest[..start].to_string(), style));
+        }
+        style = apply_sgr(style, &rest[start + 2..start + end]);
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), style));
+    }
+    Line::from(spans)
+}
+
+/// Format a duration, to the second, like `31m 12s`.
+fn duration(duration: TimeDelta) -> String {
+    format_duration(Duration::from_secs(duration.num_seconds().unsigned_abs())).to_string()
+}
+
+/// Get a line describing a bell, with the same colors as `sj now`.
+fn bell_line(data: &Subjective, bell: &BellTime) -> Line<'static> {
+    bell.format_with_time(data).map_or_else(
+        |error| Line::from(error.to_string()).red(),
+        |text| ansi_line(&text),
+    )
+}
+
+/// Get the text of a cell of the grid, with the same colors as `sj timetable show`.
+fn cell_text(cell: &Cell, current: Option<&BellTime>) -> Text<'static> {
+    let title = cell
+        .subject
+        .map_or_else(
+            || Line::from(cell.title().to_string()),
+            |subject| ansi_line(&subject.color.color(cell.title()).to_string()),
+        )
+        .bold();
+    let details = if cell.location.is_empty() {
+        cell.time()
+    } else {
+        format!("{} · {}", cell.time(), cell.location)
+    };
+    let mut text = Text::from(vec![title, Line::from(details).dim()]);
+    if !cell.bell.enabled {
+        text = text.dim().crossed_out();
+    }
+    if current.is_some_and(|current| std::ptr::eq(current, cell.bell)) {
+        text = text.reversed();
+    }
+    text
+}
+
+/// State of the dashboard.
+struct App<'a, F> {
+    data: &'a Subjective,
+    variant_offset: usize,
+    /// Gets the current time in the school's local time.
+    clock: F,
+    now: NaiveDateTime,
+    /// Week variant shown in the grid.
+    variant: usize,
+    /// Weekday selected in the grid.
+    weekday: Weekday,
+    table: TableState,
+}
+
+impl<'a, F: Fn(&Subjective) -> NaiveDateTime> App<'a, F> {
+    fn new(data: &'a Subjective, variant_offset: usize, clock: F) -> Self {
+        let now = clock(data);
+        let mut app = Self {
+            data,
+            variant_offset,
+            clock,
+            now,
+            variant: 0,
+            weekday: Weekday::Mon,
+            table: TableState::default(),
+        };
+        app.today();
+        app
+    }
+
+    /// Select today in the grid.
+    fn today(&mut self) {
+        self.variant = self
+            .data
+            .current_variant(self.now.date(), self.variant_offset)
+            .unwrap_or_default();
+        self.weekday = self.now.weekday();
+        self.table.select(None);
+    }
+
+    /// Get the grid of the selected week variant.
+    fn grid(&self) -> Option<Grid<'a>> {
+        self.data
+            .school
+            .bell_times
+            .get(self.variant)
+            .and_then(|week| Grid::new(self.data, week).ok())
+    }
+
+    /// Select the next or previous week variant, wrapping around.
+    fn step_variant(&mut self, forward: bool) {
+        let variants = self.data.school.bell_times.len().max(1);
+        self.variant = if forward {
+            (self.variant + 1) % variants
+        } else {
+            (self.variant + variants - 1) % variants
+        };
+    }
+
+    /// Select the next or previous day of the grid, moving to the next or previous week variant
+    /// past the end of the week.
+    fn step_day(&mut self, forward: bool) {
+        let Some(grid) = self.grid() else {
+            return;
+        };
+        let column = grid
+            .weekdays
+            .iter()
+            .position(|weekday| *weekday == self.weekday);
+        let next = match (column, forward) {
+            (Some(column), true) => grid.weekdays.get(column + 1).copied(),
+            (Some(column), false) => column
+                .checked_sub(1)
+                .and_then(|column| grid.weekdays.get(column).copied()),
+            (None, _) => grid.weekdays.first().copied(),
+        };
+        if let Some(weekday) = next {
+            self.weekday = weekday;
+            return;
+        }
+        self.step_variant(forward);
+        if let Some(grid) = self.grid() {
+            let weekday = if forward {
+                grid.weekdays.first()
+            } else {
+                grid.weekdays.last()
+            };
+            self.weekday = weekday.copied().unwrap_or(Weekday::Mon);
+        }
+    }
+
+    /// Handle a key press. Returns `false` if the dashboard should close.
+    fn key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Left | KeyCode::Char('h') => self.step_day(false),
+            KeyCode::Right | KeyCode::Char('l') => self.step_day(true),
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+            KeyCode::Tab | KeyCode::Char(']') => self.step_variant(true),
+            KeyCode::BackTab | KeyCode::Char('[') => self.step_variant(false),
+            KeyCode::Char('t') | KeyCode::Home => self.today(),
+            _ => {}
+        }
+        true
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, now, middle, grid, help] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(4),
+            Constraint::Percentage(35),
+            Constraint::Min(6),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [upcoming, school] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(middle);
+        frame.render_widget(
+            Line::from(vec![
+                self.data.school.name.clone().bold(),
+                format!(" {}", self.now.format("%-I:%M:%S %p %A, %B %-d, %Y")).dim(),
+            ]),
+            header,
+        );
+        self.draw_now(frame, now);
+        self.draw_upcoming(frame, upcoming);
+        self.draw_school(frame, school);
+        self.draw_grid(frame, grid);
+        frame.render_widget(
+            Line::from("←/→ day  ↑/↓ bell  Tab/Shift-Tab week  t today  q quit").dim(),
+            help,
+        );
+    }
+
+    /// Draw the current period, with a progress bar.
+    fn draw_now(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title("Now".green());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let [bell, progress] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+        let today = self.now.date();
+        let Ok(period) = self.data.current_period(self.now, self.variant_offset) else {
+            let reason = match (
+                self.data.get_override(today),
+                self.data.school.calendar.no_school_reason(today),
+            ) {
+                (Some(day_override), _) => format!("Altered day {}", day_override.name),
+                (None, Some(reason)) => format!("No school {reason}"),
+                (None, None)
+                    if matches!(self.data.get_day(today, self.variant_offset), Ok(None)) =>
+                {
+                    format!("No school on {}", weekday_name(today.weekday()))
+                }
+                (None, None) => "No period in progress".to_string(),
+            };
+            frame.render_widget(Line::from(reason).yellow(), bell);
+            return;
+        };
+        frame.render_widget(bell_line(self.data, period.bell), bell);
+        let time = self.now.time();
+        frame.render_widget(
+            LineGauge::default()
+                .filled_style(Style::new().green())
+                .ratio(period.progress(time).clamp(0., 1.))
+                .label(format!(
+                    "{} elapsed, {} remaining",
+                    duration(period.elapsed(time)),
+                    duration(period.remaining(time))
+                )),
+            progress,
+        );
+    }
+
+    /// Draw the next bells, which are the rest of today's bells, or the bells of the next day
+    /// with school.
+    fn draw_upcoming(&self, frame: &mut Frame, area: Rect) {
+        let mut bells = self
+            .data
+            .bells_from(self.now, self.variant_offset)
+            .peekable();
+        let date = bells.peek().map(|(date, _)| *date);
+        let title = match date {
+            Some(date) if date == self.now.date() => "Upcoming".to_string(),
+            Some(date) => format!("Upcoming {}", date.format("%A, %B %-d")),
+            None => "No upcoming bells".to_string(),
+        };
+        let items = bells
+            .take_while(|(bell_date, _)| Some(*bell_date) == date)
+            .map(|(bell_date, bell)| {
+                let until = self
+                    .data
+                    .duration_between(self.now, bell_date.and_time(bell.time));
+                let mut line = bell_line(self.data, bell);
+                line.spans
+                    .insert(0, Span::raw(format!("{:>10} ", duration(until))).yellow());
+                line
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(
+            List::new(items).block(Block::bordered().title(title.green())),
+            area,
+        );
+    }
+
+    /// Draw the priority notices and links of the school.
+    fn draw_school(&self, frame: &mut Frame, area: Rect) {
+        let mut text = Text::default();
+        for notice in self
+            .data
+            .school
+            .notices
+            .iter()
+            .filter(|notice| notice.priority)
+        {
+            text.push_line(Line::from(notice.title.clone()).yellow().bold());
+            text.extend(Text::from(notice.content.clone()));
+        }
+        if !self.data.school.links.is_empty() {
+            if !text.lines.is_empty() {
+                text.push_line("");
+            }
+            text.push_line(Line::from("Links").green());
+            for link in &self.data.school.links {
+                text.push_line(Line::from(vec![
+                    Span::raw(link.name.clone()),
+                    Span::raw(format!(" {}", link.destination)).dim(),
+                ]));
+            }
+        }
+        if text.lines.is_empty() {
+            text.push_line(Line::from("No priority notices or links.").dim());
+        }
+        frame.render_widget(
+            Paragraph::new(text)
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().title("Notices".green())),
+            area,
+        );
+    }
+
+    /// Draw the grid of the selected week variant, with the selected day highlighted.
+    fn draw_grid(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(grid) = self.grid() else {
+            frame.render_widget(
+                Paragraph::new("There are no week variants.").block(Block::bordered()),
+                area,
+            );
+            return;
+        };
+        let this_week = self
+            .data
+            .current_variant(self.now.date(), self.variant_offset)
+            == Some(self.variant);
+        let current = self
+            .data
+            .current_period(self.now, self.variant_offset)
+            .ok()
+            .map(|period| period.bell);
+        let column = grid
+            .weekdays
+            .iter()
+            .position(|weekday| *weekday == self.weekday);
+        let header = Row::new(grid.weekdays.iter().map(|weekday| {
+            let name = Line::from(weekday_name(*weekday)).bold();
+            if this_week && *weekday == self.now.weekday() {
+                name.blue()
+            } else {
+                name
+            }
+        }));
+        let rows = grid.rows.iter().map(|row| {
+            Row::new(row.iter().map(|cell| {
+                TableCell::from(
+                    cell.as_ref()
+                        .map(|cell| cell_text(cell, current))
+                        .unwrap_or_default(),
+                )
+            }))
+            .height(2)
+        });
+        let title = format!(
+            "{} ({}/{}){}",
+            grid.week.name,
+            self.variant + 1,
+            self.data.school.bell_times.len(),
+            if this_week { " this week" } else { "" }
+        );
+        let table = Table::new(rows, vec![Constraint::Fill(1); grid.weekdays.len()])
+            .header(header)
+            .block(Block::bordered().title(title.green()))
+            .column_highlight_style(Style::new().on_dark_gray())
+            .row_highlight_style(Style::new().underlined());
+        self.table.select_column(column);
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+
+    fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
+        loop {
+            self.now = (self.clock)(self.data);
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(TICK)?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.key(key)
+            {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Show the dashboard until it is closed. The time is given by `clock`, in the school's local time.
+pub fn run(
+    data: &Subjective,
+    variant_offset: usize,
+    clock: impl Fn(&Subjective) -> NaiveDateTime,
+) -> Result<()> {
+    control::set_override(true);
+    let terminal = ratatui::init();
+    let result = App::new(data, variant_offset, clock).run(terminal);
+    ratatui::restore();
+    control::unset_override();
+    result
+}
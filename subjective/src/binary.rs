@@ -0,0 +1,296 @@
+#![cfg(feature = "binary")]
+
+#[cfg(feature = "std")]
+use chrono::{Datelike, Timelike};
+#[cfg(not(feature = "std"))]
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(not(feature = "std"))]
+use crate::Subjective;
+use crate::school::bells::BellData;
+#[cfg(not(feature = "std"))]
+use crate::school::{Day, School, Week, bells::BellTime, cycle::CycleAnchor};
+
+/// Bytes at the start of binary Subjective data.
+pub const MAGIC: [u8; 4] = *b"SJTT";
+
+/// Version of the binary format which is written and read by this version of the library.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Errors that can occur when reading binary Subjective data.
+#[derive(Error, Debug)]
+pub enum DecodeError {
+    /// The data doesn't start with [`MAGIC`].
+    #[error("The data isn't binary Subjective data.")]
+    InvalidMagic,
+    /// The data was written in a version of the binary format which isn't supported.
+    #[error("Version {0} of the binary format isn't supported.")]
+    UnsupportedVersion(u8),
+    /// The storage given to [`from_binary`] is too small for the data.
+    #[error("The storage is too small for {0:?}.")]
+    StorageTooSmall(Sizes),
+    /// A bell has a type which isn't part of the binary format.
+    #[error("Unknown bell type {0}.")]
+    InvalidBellType(u8),
+    /// A time or date is out of range.
+    #[error("A time or date is out of range.")]
+    OutOfRange,
+    /// The data couldn't be decoded.
+    #[error(transparent)]
+    PostcardError(#[from] postcard::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Number of week variants, days, and bells in binary Subjective data, which is the storage needed
+/// to read it with [`from_binary`].
+pub struct Sizes {
+    /// Number of week variants.
+    pub weeks: usize,
+    /// Number of days, across all week variants.
+    pub days: usize,
+    /// Number of bells, across all days.
+    pub bells: usize,
+}
+
+/// Start of binary Subjective data, after [`MAGIC`]. It is followed by each week variant, each of
+/// its days as the number of bells, and each of those bells.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u8,
+    weeks: u32,
+    days: u32,
+    bells: u32,
+    /// Days since 0001-01-01 of the anchored date, and the variant active in its week.
+    cycle_anchor: Option<(i32, u32)>,
+}
+
+/// Week variant, followed by its days. Days after the last day have no school.
+#[derive(Serialize, Deserialize)]
+struct WireWeek {
+    cyclical: bool,
+    days: u8,
+}
+
+/// Bell, with its time in seconds from midnight.
+#[derive(Serialize, Deserialize)]
+struct WireBell {
+    time: u32,
+    bell_type: u8,
+    enabled: bool,
+}
+
+#[cfg(feature = "std")]
+/// Get the number of a bell type in the binary format. Types which don't exist in `no_std`, like
+/// the subject and location of classes, aren't kept.
+const fn bell_type(bell_data: Option<&BellData>) -> u8 {
+    match bell_data {
+        None => 0,
+        Some(BellData::Class { .. }) => 1,
+        Some(BellData::Time) => 2,
+        Some(BellData::Break) => 3,
+        Some(BellData::Study) => 4,
+        Some(BellData::Pause) => 5,
+        Some(BellData::Other { .. }) => 6,
+    }
+}
+
+#[cfg(not(feature = "std"))]
+const fn bell_data(bell_type: u8) -> Result<Option<BellData>, DecodeError> {
+    Ok(match bell_type {
+        0 => None,
+        1 => Some(BellData::Class {}),
+        2 => Some(BellData::Time),
+        3 => Some(BellData::Break),
+        4 => Some(BellData::Study),
+        5 => Some(BellData::Pause),
+        6 => Some(BellData::Other {}),
+        _ => return Err(DecodeError::InvalidBellType(bell_type)),
+    })
+}
+
+fn size(count: u32) -> usize {
+    usize::try_from(count).unwrap_or(usize::MAX)
+}
+
+/// Reader of the values of binary Subjective data, in order.
+struct Reader<'d>(&'d [u8]);
+
+impl<'d> Reader<'d> {
+    fn take<T: Deserialize<'d>>(&mut self) -> Result<T, DecodeError> {
+        let (value, rest) = postcard::take_from_bytes(self.0)?;
+        self.0 = rest;
+        Ok(value)
+    }
+
+    fn header(&mut self) -> Result<Header, DecodeError> {
+        if self.take::<[u8; 4]>().ok() != Some(MAGIC) {
+            return Err(DecodeError::InvalidMagic);
+        }
+        let header: Header = self.take()?;
+        if header.version != FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedVersion(header.version));
+        }
+        Ok(header)
+    }
+}
+
+/// Get the number of week variants, days, and bells in binary Subjective data, so that storage for
+/// [`from_binary`] can be checked or allocated.
+///
+/// # Errors
+///
+/// This function will return an error if the data isn't binary Subjective data in a supported
+/// version of the format.
+pub fn sizes(bytes: &[u8]) -> Result<Sizes, DecodeError> {
+    let header = Reader(bytes).header()?;
+    Ok(Sizes {
+        weeks: size(header.weeks),
+        days: size(header.days),
+        bells: size(header.bells),
+    })
+}
+
+/// Write the timetable of Subjective data in a compact binary format, which can be read by
+/// [`from_binary`] in `no_std` builds without an allocator.
+///
+/// Only the parts of the data which exist in `no_std` are written: the week variants, the time,
+/// type, and enabled status of each bell, and the cycle anchor.
+///
+/// # Errors
+///
+/// This function will return an error if the data has more week variants, days, or bells than the
+/// format can hold.
+#[cfg(feature = "std")]
+pub fn to_binary(data: &crate::Subjective) -> Result<Vec<u8>, postcard::Error> {
+    let weeks = data
+        .school
+        .bell_times
+        .iter()
+        .map(|week| (week, week.days.school_days()))
+        .collect::<Vec<_>>();
+    let days = weeks.iter().map(|(_, days)| days.len()).sum::<usize>();
+    let bells = weeks
+        .iter()
+        .flat_map(|(_, days)| days)
+        .map(|day| day.len())
+        .sum::<usize>();
+    let count =
+        |count: usize| u32::try_from(count).map_err(|_| postcard::Error::SerializeBufferFull);
+    let mut bytes = MAGIC.to_vec();
+    bytes = postcard::to_extend(
+        &Header {
+            version: FORMAT_VERSION,
+            weeks: count(weeks.len())?,
+            days: count(days)?,
+            bells: count(bells)?,
+            cycle_anchor: data
+                .school
+                .cycle_anchor
+                .map(|anchor| {
+                    u32::try_from(anchor.variant)
+                        .map(|variant| (anchor.date.num_days_from_ce(), variant))
+                })
+                .transpose()
+                .map_err(|_| postcard::Error::SerializeBufferFull)?,
+        },
+        bytes,
+    )?;
+    for (week, days) in weeks {
+        bytes = postcard::to_extend(
+            &WireWeek {
+                cyclical: week.cyclical,
+                days: u8::try_from(days.len()).map_err(|_| postcard::Error::SerializeBufferFull)?,
+            },
+            bytes,
+        )?;
+        for day in days {
+            bytes = postcard::to_extend(&count(day.len())?, bytes)?;
+            for bell in day {
+                bytes = postcard::to_extend(
+                    &WireBell {
+                        time: bell.time.num_seconds_from_midnight(),
+                        bell_type: bell_type(bell.bell_data.as_ref()),
+                        enabled: bell.enabled,
+                    },
+                    bytes,
+                )?;
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// Read binary Subjective data written by [`to_binary`], without an allocator.
+///
+/// The week variants, days, and bells are read into the given storage, which the returned data
+/// borrows. Use [`sizes`] to find how much storage is needed.
+///
+/// # Errors
+///
+/// This function will return an error if the data isn't binary Subjective data in a supported
+/// version of the format, if it is invalid, or if the storage is too small
+/// ([`DecodeError::StorageTooSmall`]).
+#[cfg(not(feature = "std"))]
+pub fn from_binary<'a, 'b, 'c>(
+    bytes: &[u8],
+    weeks: &'a mut [Week<'b, 'c>],
+    days: &'b mut [Day<'c>],
+    bells: &'c mut [BellTime],
+) -> Result<Subjective<'a, 'b, 'c>, DecodeError> {
+    let mut reader = Reader(bytes);
+    let header = reader.header()?;
+    let sizes = Sizes {
+        weeks: size(header.weeks),
+        days: size(header.days),
+        bells: size(header.bells),
+    };
+    if sizes.weeks > weeks.len() || sizes.days > days.len() || sizes.bells > bells.len() {
+        return Err(DecodeError::StorageTooSmall(sizes));
+    }
+    let cycle_anchor = match header.cycle_anchor {
+        Some((date, variant)) => Some(CycleAnchor {
+            date: NaiveDate::from_num_days_from_ce_opt(date).ok_or(DecodeError::OutOfRange)?,
+            variant: size(variant),
+        }),
+        None => None,
+    };
+    let (weeks, _) = weeks.split_at_mut(sizes.weeks);
+    let mut days = days;
+    let mut bells = bells;
+    for week in weeks.iter_mut() {
+        let wire: WireWeek = reader.take()?;
+        let (week_days, rest) = core::mem::take(&mut days)
+            .split_at_mut_checked(usize::from(wire.days))
+            .ok_or(DecodeError::StorageTooSmall(sizes))?;
+        days = rest;
+        for day in week_days.iter_mut() {
+            let count: u32 = reader.take()?;
+            let (day_bells, rest) = core::mem::take(&mut bells)
+                .split_at_mut_checked(size(count))
+                .ok_or(DecodeError::StorageTooSmall(sizes))?;
+            bells = rest;
+            for bell in day_bells.iter_mut() {
+                let wire: WireBell = reader.take()?;
+                *bell = BellTime {
+                    time: NaiveTime::from_num_seconds_from_midnight_opt(wire.time, 0)
+                        .ok_or(DecodeError::OutOfRange)?,
+                    bell_data: bell_data(wire.bell_type)?,
+                    enabled: wire.enabled,
+                };
+            }
+            *day = day_bells;
+        }
+        *week = Week {
+            days: week_days,
+            cyclical: wire.cyclical,
+        };
+    }
+    Ok(Subjective {
+        school: School {
+            bell_times: weeks,
+            cycle_anchor,
+        },
+    })
+}
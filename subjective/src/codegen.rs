@@ -0,0 +1,177 @@
+#![cfg(feature = "std")]
+
+use core::fmt::{self, Write};
+use std::{
+    env, fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+use chrono::{Datelike, Timelike};
+use thiserror::Error;
+
+use crate::{
+    LoadDataError, Subjective,
+    school::bells::{BellData, BellTime},
+    source::{CompileError, compile},
+};
+
+/// Errors that can occur when generating code with [`generate`].
+#[derive(Error, Debug)]
+pub enum CodegenError {
+    /// The path of the timetable is absolute or contains `..`, so the generated code would be
+    /// written outside of `OUT_DIR`.
+    #[error("The timetable at {0:?} must be given relative to the package, without \"..\".")]
+    InvalidPath(PathBuf),
+    /// The timetable couldn't be read.
+    #[error("Couldn't read the timetable at {0:?}.")]
+    ReadError(PathBuf, io::Error),
+    /// The timetable couldn't be loaded.
+    #[error(transparent)]
+    LoadDataError(#[from] LoadDataError),
+    /// The timetable source couldn't be compiled.
+    #[error(transparent)]
+    CompileError(#[from] CompileError),
+    /// The `OUT_DIR` environment variable isn't set, because [`generate`] wasn't called from a
+    /// build script.
+    #[error("OUT_DIR isn't set. Timetables can only be generated from a build script.")]
+    MissingOutDir,
+    /// The generated code couldn't be written.
+    #[error("Couldn't write the generated code to {0:?}.")]
+    WriteError(PathBuf, io::Error),
+    /// The generated code couldn't be formatted.
+    #[error(transparent)]
+    FmtError(#[from] fmt::Error),
+}
+
+/// Get the path of a variant of a bell type in generated code.
+const fn bell_data(bell_data: Option<&BellData>) -> &'static str {
+    match bell_data {
+        None => "None",
+        Some(BellData::Class { .. }) => "Some(BellData::Class {})",
+        Some(BellData::Time) => "Some(BellData::Time)",
+        Some(BellData::Break) => "Some(BellData::Break)",
+        Some(BellData::Study) => "Some(BellData::Study)",
+        Some(BellData::Pause) => "Some(BellData::Pause)",
+        Some(BellData::Other { .. }) => "Some(BellData::Other {})",
+    }
+}
+
+fn write_bell(output: &mut String, bell: &BellTime) -> fmt::Result {
+    writeln!(
+        output,
+        "                        BellTime {{ time: time({}, {}, {}), bell_data: {}, enabled: {} }},",
+        bell.time.hour(),
+        bell.time.minute(),
+        bell.time.second(),
+        bell_data(bell.bell_data.as_ref()),
+        bell.enabled
+    )
+}
+
+/// Generate a Rust expression which creates the timetable of Subjective data as `no_std` data,
+/// which can be the value of a `static`.
+///
+/// Only the parts of the data which exist in `no_std` are kept: the week variants, the time,
+/// type, and enabled status of each bell, and the cycle anchor. The expression refers to the
+/// library as `::subjective`.
+///
+/// # Errors
+///
+/// This function will return an error if the code couldn't be written.
+pub fn to_rust(data: &Subjective) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+    writeln!(output, "{{")?;
+    writeln!(output, "    #[allow(unused_imports)]")?;
+    writeln!(
+        output,
+        "    use ::subjective::{{Subjective, chrono::{{NaiveDate, NaiveTime}}, school::{{School, Week, bells::{{BellData, BellTime}}, cycle::CycleAnchor}}}};"
+    )?;
+    writeln!(
+        output,
+        "    const fn time(hour: u32, minute: u32, second: u32) -> NaiveTime {{"
+    )?;
+    writeln!(
+        output,
+        "        match NaiveTime::from_hms_opt(hour, minute, second) {{ Some(time) => time, None => panic!(\"invalid time\") }}"
+    )?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "    Subjective {{")?;
+    writeln!(output, "        school: School {{")?;
+    writeln!(output, "            bell_times: &[")?;
+    for week in &data.school.bell_times {
+        writeln!(
+            output,
+            "                Week {{ cyclical: {}, days: &[",
+            week.cyclical
+        )?;
+        for day in week.days.school_days() {
+            if day.is_empty() {
+                writeln!(output, "                    &[],")?;
+                continue;
+            }
+            writeln!(output, "                    &[")?;
+            for bell in day {
+                write_bell(&mut output, bell)?;
+            }
+            writeln!(output, "                    ],")?;
+        }
+        writeln!(output, "                ] }},")?;
+    }
+    writeln!(output, "            ],")?;
+    match data.school.cycle_anchor {
+        Some(anchor) => writeln!(
+            output,
+            "            cycle_anchor: Some(CycleAnchor {{ date: match NaiveDate::from_ymd_opt({}, {}, {}) {{ Some(date) => date, None => panic!(\"invalid date\") }}, variant: {} }}),",
+            anchor.date.year(),
+            anchor.date.month(),
+            anchor.date.day(),
+            anchor.variant
+        )?,
+        None => writeln!(output, "            cycle_anchor: None,")?,
+    }
+    writeln!(output, "        }},")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}")?;
+    Ok(output)
+}
+
+/// Generate the code for [`crate::include_timetable`] from a build script.
+///
+/// The timetable is read from `file`, relative to the package, as Subjective data, or as a
+/// timetable source if it ends in `.toml`. The code is written to `$OUT_DIR/<file>.rs`, and Cargo
+/// is told to run the build script again when the timetable changes.
+///
+/// # Errors
+///
+/// This function will return an error if `file` is absolute or contains `..`, if the timetable
+/// can't be read, loaded, or compiled, or if it isn't called from a build script.
+pub fn generate(file: impl AsRef<Path>) -> Result<(), CodegenError> {
+    let file = file.as_ref();
+    if !file
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(CodegenError::InvalidPath(file.into()));
+    }
+    let text =
+        fs::read_to_string(file).map_err(|error| CodegenError::ReadError(file.into(), error))?;
+    let data = if file
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        compile(&text)?
+    } else {
+        Subjective::from_json(&text)?.0
+    };
+    let out_dir = env::var_os("OUT_DIR").ok_or(CodegenError::MissingOutDir)?;
+    let mut output = PathBuf::from(out_dir).join(file);
+    output.as_mut_os_string().push(".rs");
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| CodegenError::WriteError(output.clone(), error))?;
+    }
+    fs::write(&output, to_rust(&data)?)
+        .map_err(|error| CodegenError::WriteError(output.clone(), error))?;
+    println!("cargo::rerun-if-changed={}", file.display());
+    Ok(())
+}
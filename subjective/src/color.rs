@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 /// A color with red, green, and blue components.
 pub struct Color {
     /// Red component. (`0_f32..=1_f32`)
@@ -33,4 +34,49 @@ impl Color {
             (self.blue * 255_f32) as u8,
         )
     }
+
+    #[cfg(feature = "std")]
+    #[must_use]
+    /// Get the color as a hex color, like `#4a7e80`, for status bars and other tools.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subjective::color::Color;
+    ///
+    /// assert_eq!(Color::SUBJECTIVE_BLUE.to_hex(), "#3685ff");
+    /// ```
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_hex(&self) -> String {
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            (self.red * 255_f32) as u8,
+            (self.green * 255_f32) as u8,
+            (self.blue * 255_f32) as u8
+        )
+    }
+
+    #[cfg(feature = "std")]
+    /// Generate the color of the subject at an index, spreading hues evenly around the color wheel.
+    #[allow(clippy::cast_precision_loss, clippy::many_single_char_names)]
+    pub(crate) fn generated(index: usize) -> Self {
+        let hue = (index as f32 * 0.618_034).fract() * 6.;
+        let (saturation, value) = (0.6_f32, 0.85_f32);
+        let chroma = value * saturation;
+        let x = chroma * (1. - (hue % 2. - 1.).abs());
+        let (red, green, blue) = match hue {
+            hue if hue < 1. => (chroma, x, 0.),
+            hue if hue < 2. => (x, chroma, 0.),
+            hue if hue < 3. => (0., chroma, x),
+            hue if hue < 4. => (0., x, chroma),
+            hue if hue < 5. => (x, 0., chroma),
+            _ => (chroma, 0., x),
+        };
+        let m = value - chroma;
+        Self {
+            red: red + m,
+            green: green + m,
+            blue: blue + m,
+        }
+    }
 }
@@ -0,0 +1,4 @@
+/// CSV export of bells and subjects, for editing in spreadsheets.
+pub mod csv;
+/// iCalendar (`.ics`) export.
+pub mod ical;
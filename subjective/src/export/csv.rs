@@ -0,0 +1,153 @@
+#![cfg(feature = "std")]
+
+use std::io::Write;
+
+use chrono::Weekday;
+use csv::Writer;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{Subjective, school::bells::BellData};
+
+/// Errors that can occur when exporting bells and subjects with [`to_csv`].
+#[derive(Error, Debug)]
+pub enum ExportError {
+    /// The subject with the given ID was not found. This means that the data is invalid.
+    #[error("No subject found matching \"{0}\". This means that your Subjective data is invalid.")]
+    SubjectNotFound(Uuid),
+    /// An error occurred while writing a sheet.
+    #[error(transparent)]
+    CsvError(#[from] csv::Error),
+    /// An error occurred while flushing a sheet.
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+/// Row of the bells sheet.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BellRow {
+    pub variant: String,
+    pub weekday: Weekday,
+    pub time: String,
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub id: Option<Uuid>,
+}
+
+/// Row of the subjects sheet.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SubjectRow {
+    pub name: String,
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub locations: String,
+    #[serde(default)]
+    pub id: Option<Uuid>,
+}
+
+/// Separator between the locations of a subject.
+pub(crate) const LOCATION_SEPARATOR: char = ';';
+
+/// Get the name of a kind of bell in the `type` column.
+fn bell_type(bell_data: Option<&BellData>) -> &str {
+    match bell_data {
+        None => "",
+        Some(BellData::Class { .. }) => "class",
+        Some(BellData::Time) => "time",
+        Some(BellData::Break) => "break",
+        Some(BellData::Study) => "study",
+        Some(BellData::Pause) => "pause",
+        Some(BellData::Other { name }) => name,
+    }
+}
+
+/// Export the bells and subjects of a timetable as two CSV sheets, for editing in spreadsheets.
+///
+/// The bells sheet has one row per bell, with the columns `variant`, `weekday`, `time`, `name`,
+/// `type`, `subject`, `location`, `enabled`, `icon`, and `id`:
+///
+/// - `variant` is the name of the week variant, and `weekday` is like `Mon`.
+/// - `time` is like `08:40`.
+/// - `type` is `class`, `time`, `break`, `study`, `pause`, or empty for a bell without a type.
+///   Bells of a type which isn't known have the name of the type.
+/// - `subject` is the name of the subject and `location` is the classroom, for classes.
+/// - `enabled` is `true` or `false`.
+/// - `icon` is the SF Symbols icon of the bell, or empty for the icon of its type.
+/// - `id` is the UUID of the bell.
+///
+/// The subjects sheet has one row per subject, with the columns `name`, `red`, `green`, `blue`,
+/// `icon`, `locations`, and `id`. The color components are between 0 and 1, and locations are
+/// separated by `;`.
+///
+/// # Errors
+///
+/// This function will return an error if a class refers to a subject which doesn't exist
+/// ([`ExportError::SubjectNotFound`]), or if a sheet can't be written.
+pub fn to_csv(
+    data: &Subjective,
+    bells: impl Write,
+    subjects: impl Write,
+) -> Result<(), ExportError> {
+    let mut writer = Writer::from_writer(bells);
+    for week in &data.school.bell_times {
+        for (weekday, day) in week.days.iter() {
+            for bell in day {
+                let (subject, location) = match &bell.bell_data {
+                    Some(BellData::Class {
+                        subject_id,
+                        location,
+                    }) => (
+                        data.get_subject(*subject_id)
+                            .ok_or(ExportError::SubjectNotFound(*subject_id))?
+                            .name
+                            .clone(),
+                        location.clone(),
+                    ),
+                    _ => (String::new(), String::new()),
+                };
+                writer.serialize(BellRow {
+                    variant: week.name.clone(),
+                    weekday,
+                    time: bell.time.format("%H:%M").to_string(),
+                    name: bell.name.clone(),
+                    kind: bell_type(bell.bell_data.as_ref()).to_string(),
+                    subject,
+                    location,
+                    enabled: Some(bell.enabled),
+                    icon: bell.icon.clone(),
+                    id: (!bell.id.is_nil()).then_some(bell.id),
+                })?;
+            }
+        }
+    }
+    writer.flush()?;
+    let mut writer = Writer::from_writer(subjects);
+    for subject in &data.subjects {
+        writer.serialize(SubjectRow {
+            name: subject.name.clone(),
+            red: subject.color.red,
+            green: subject.color.green,
+            blue: subject.color.blue,
+            icon: subject.icon.clone(),
+            locations: subject.locations.join(&LOCATION_SEPARATOR.to_string()),
+            id: Some(subject.id),
+        })?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,317 @@
+#![cfg(feature = "std")]
+
+use core::fmt::Write;
+use std::collections::BTreeMap;
+
+use chrono::{
+    DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    Subjective,
+    school::{bells::BellData, period::Period},
+};
+
+/// Errors that can occur when exporting a timetable with [`to_ical`].
+#[derive(Error, Debug)]
+pub enum ExportError {
+    /// The date range ends before it starts.
+    #[error("The date range ends on {1}, before it starts on {0}.")]
+    InvertedDateRange(NaiveDate, NaiveDate),
+    /// The subject with the given ID was not found. This means that the data is invalid.
+    #[error("No subject found matching \"{0}\". This means that your Subjective data is invalid.")]
+    SubjectNotFound(Uuid),
+    /// An error occurred while writing the calendar.
+    #[error(transparent)]
+    FmtError(#[from] core::fmt::Error),
+}
+
+/// Class which takes place at the same time on one or more dates.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Class {
+    weekday: u32,
+    start: NaiveTime,
+    end: NaiveTime,
+    summary: String,
+    location: String,
+    bell: Uuid,
+}
+
+/// Dates which are the same number of weeks apart, which can be described by one `RRULE`.
+struct Run {
+    first: NaiveDate,
+    interval: i64,
+    count: i64,
+}
+
+/// Split ascending dates into runs which repeat at a fixed number of weeks.
+/// The number of weeks is the smallest gap between the dates, which is the length of the cycle
+/// for classes in cyclical timetables.
+fn runs(dates: &[NaiveDate]) -> Vec<Run> {
+    let interval = dates
+        .windows(2)
+        .map(|dates| (dates[1] - dates[0]).num_weeks())
+        .min()
+        .unwrap_or(1);
+    let mut runs: Vec<Run> = Vec::new();
+    for &date in dates {
+        match runs.last_mut() {
+            Some(run) if (date - run.first).num_weeks() == interval * run.count => {
+                run.count += 1;
+            }
+            _ => runs.push(Run {
+                first: date,
+                interval,
+                count: 1,
+            }),
+        }
+    }
+    runs
+}
+
+/// Escape text for use in a property value, as described in RFC 5545.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Write a content line, folded to lines of at most 75 octets, as described in RFC 5545.
+fn write_line(output: &mut String, line: &str) -> core::fmt::Result {
+    let mut length = 0;
+    for character in line.chars() {
+        if length + character.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            length = 1;
+        }
+        output.push(character);
+        length += character.len_utf8();
+    }
+    output.write_str("\r\n")
+}
+
+/// Get the `BYDAY` value of a weekday, as described in RFC 5545.
+const fn by_day(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// Format an offset from UTC in seconds, like `+1100`, as described in RFC 5545.
+fn format_offset(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    let seconds = offset % 60;
+    let mut output = format!("{sign}{:02}{:02}", offset / 3600, offset % 3600 / 60);
+    if seconds != 0 {
+        let _ = write!(output, "{seconds:02}");
+    }
+    output
+}
+
+/// Write a `VTIMEZONE` for a timezone between two dates, as described in RFC 5545.
+///
+/// The offset at the start of the range is the first observance, and each change of offset in
+/// the range is another observance, so that events repeating across daylight saving changes keep
+/// their local times.
+fn write_timezone(
+    output: &mut String,
+    timezone: Tz,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> core::fmt::Result {
+    let offset = |instant: NaiveDateTime| timezone.offset_from_utc_datetime(&instant);
+    let seconds = |instant: NaiveDateTime| offset(instant).fix().local_minus_utc();
+    // Include a day on each side, as the range is in local time.
+    let start = from.pred_opt().unwrap_or(from).and_time(NaiveTime::MIN);
+    let end = to.succ_opt().unwrap_or(to).and_time(NaiveTime::MIN) + TimeDelta::days(1);
+    let mut onsets = vec![(start, seconds(start))];
+    let mut instant = start;
+    while instant < end {
+        let next = instant + TimeDelta::hours(1);
+        if seconds(next) != seconds(instant) {
+            let (mut before, mut after) = (instant, next);
+            while after - before > TimeDelta::minutes(1) {
+                let middle = before + (after - before) / 2;
+                if seconds(middle) == seconds(before) {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            onsets.push((after, seconds(before)));
+        }
+        instant = next;
+    }
+    write_line(output, "BEGIN:VTIMEZONE")?;
+    write_line(output, &format!("TZID:{}", timezone.name()))?;
+    for (onset, offset_from) in onsets {
+        let observance = offset(onset);
+        let kind = if observance.dst_offset().is_zero() {
+            "STANDARD"
+        } else {
+            "DAYLIGHT"
+        };
+        write_line(output, &format!("BEGIN:{kind}"))?;
+        write_line(
+            output,
+            &format!(
+                "DTSTART:{}",
+                (onset + TimeDelta::seconds(offset_from.into())).format("%Y%m%dT%H%M%S")
+            ),
+        )?;
+        write_line(
+            output,
+            &format!("TZOFFSETFROM:{}", format_offset(offset_from)),
+        )?;
+        write_line(
+            output,
+            &format!(
+                "TZOFFSETTO:{}",
+                format_offset(observance.fix().local_minus_utc())
+            ),
+        )?;
+        if let Some(name) = observance.abbreviation() {
+            write_line(output, &format!("TZNAME:{}", escape(name)))?;
+        }
+        write_line(output, &format!("END:{kind}"))?;
+    }
+    write_line(output, "END:VTIMEZONE")
+}
+
+/// Write a `VEVENT` for a run of a class.
+fn write_event(
+    output: &mut String,
+    class: &Class,
+    run: &Run,
+    stamp: DateTime<Utc>,
+    timezone: Option<Tz>,
+) -> core::fmt::Result {
+    let format_time = |time: NaiveTime| {
+        let date_time = run.first.and_time(time).format("%Y%m%dT%H%M%S");
+        timezone.map_or_else(
+            || format!(":{date_time}"),
+            |timezone| format!(";TZID={}:{date_time}", timezone.name()),
+        )
+    };
+    write_line(output, "BEGIN:VEVENT")?;
+    write_line(
+        output,
+        &format!(
+            "UID:{}-{}@subjective",
+            class.bell,
+            run.first.and_time(class.start).format("%Y%m%dT%H%M%S")
+        ),
+    )?;
+    write_line(
+        output,
+        &format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+    )?;
+    write_line(output, &format!("DTSTART{}", format_time(class.start)))?;
+    write_line(output, &format!("DTEND{}", format_time(class.end)))?;
+    if run.count > 1 {
+        write_line(
+            output,
+            &format!(
+                "RRULE:FREQ=WEEKLY;INTERVAL={};COUNT={};BYDAY={}",
+                run.interval,
+                run.count,
+                by_day(run.first.weekday())
+            ),
+        )?;
+    }
+    write_line(output, &format!("SUMMARY:{}", escape(&class.summary)))?;
+    if !class.location.is_empty() {
+        write_line(output, &format!("LOCATION:{}", escape(&class.location)))?;
+    }
+    write_line(output, "END:VEVENT")
+}
+
+/// Export the classes of a timetable between two dates, inclusive, as an iCalendar (`.ics`) file.
+///
+/// Each class becomes a `VEVENT` spanning from its bell to the next enabled bell, with the
+/// subject as the summary and the classroom as the location. Classes which repeat every week, or
+/// every few weeks in cyclical timetables, are combined into one event with an `RRULE`.
+/// Days without school, like holidays, interrupt the repetition.
+///
+/// Times are in the school's timezone if it has one, which is described by a `VTIMEZONE`, and
+/// floating local times otherwise.
+/// `stamp` is used as the `DTSTAMP` of every event, and is normally the current time.
+///
+/// # Errors
+///
+/// This function will return an error if the date range ends before it starts
+/// ([`ExportError::InvertedDateRange`]), or if a class refers to a subject which doesn't exist
+/// ([`ExportError::SubjectNotFound`]).
+pub fn to_ical(
+    data: &Subjective,
+    from: NaiveDate,
+    to: NaiveDate,
+    variant_offset: usize,
+    stamp: DateTime<Utc>,
+) -> Result<String, ExportError> {
+    if to < from {
+        return Err(ExportError::InvertedDateRange(from, to));
+    }
+    let mut classes: BTreeMap<Class, Vec<NaiveDate>> = BTreeMap::new();
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        let Ok(Some(day)) = data.get_day(date, variant_offset) else {
+            continue;
+        };
+        for period in Period::from_day(day) {
+            let Some(BellData::Class {
+                subject_id,
+                location,
+            }) = &period.bell.bell_data
+            else {
+                continue;
+            };
+            let subject = data
+                .get_subject(*subject_id)
+                .ok_or(ExportError::SubjectNotFound(*subject_id))?;
+            classes
+                .entry(Class {
+                    weekday: date.weekday().num_days_from_monday(),
+                    start: period.start,
+                    end: period.end,
+                    summary: subject.name.clone(),
+                    location: location.clone(),
+                    bell: period.bell.id,
+                })
+                .or_default()
+                .push(date);
+        }
+    }
+    let timezone = data.school.tz();
+    let mut output = String::new();
+    write_line(&mut output, "BEGIN:VCALENDAR")?;
+    write_line(&mut output, "VERSION:2.0")?;
+    write_line(&mut output, "PRODID:-//SubjectiveLabs//Subjective//EN")?;
+    write_line(&mut output, "CALSCALE:GREGORIAN")?;
+    write_line(
+        &mut output,
+        &format!("X-WR-CALNAME:{}", escape(&data.school.name)),
+    )?;
+    if let Some(timezone) = timezone {
+        write_line(&mut output, &format!("X-WR-TIMEZONE:{}", timezone.name()))?;
+        write_timezone(&mut output, timezone, from, to)?;
+    }
+    for (class, dates) in &classes {
+        for run in runs(dates) {
+            write_event(&mut output, class, &run, stamp, timezone)?;
+        }
+    }
+    write_line(&mut output, "END:VCALENDAR")?;
+    Ok(output)
+}
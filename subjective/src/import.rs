@@ -0,0 +1,4 @@
+/// CSV import of bells and subjects, in the layout written by [`crate::export::csv::to_csv`].
+pub mod csv;
+/// iCalendar (`.ics`) import.
+pub mod ical;
@@ -0,0 +1,202 @@
+#![cfg(feature = "std")]
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+};
+
+use chrono::NaiveTime;
+use csv::{Position, Reader, StringRecord};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    Subjective,
+    color::Color,
+    export::csv::{BellRow, LOCATION_SEPARATOR, SubjectRow},
+    school::{
+        Days, Week,
+        bells::{BellData, BellTime},
+    },
+    subjects::Subject,
+    unknown::UnknownFields,
+};
+
+/// Errors that can occur when importing bells and subjects with [`from_csv`].
+#[derive(Error, Debug)]
+pub enum ImportError {
+    /// A row couldn't be read.
+    #[error(transparent)]
+    CsvError(#[from] csv::Error),
+    /// A time couldn't be parsed.
+    #[error("Couldn't parse the time \"{1}\" on line {0}.")]
+    InvalidTime(u64, String),
+    /// A class refers to a subject which isn't in the subjects sheet.
+    #[error("No subject found matching \"{1}\" on line {0}.")]
+    SubjectNotFound(u64, String),
+    /// Two subjects have the same name, so classes can't refer to them.
+    #[error("There is more than one subject named \"{0}\".")]
+    DuplicateSubject(String),
+    /// Two week variants have the same name, so rows can't refer to them.
+    #[error("There is more than one week variant named \"{0}\".")]
+    DuplicateVariant(String),
+}
+
+/// Read the rows of a sheet, with the line each row starts on.
+fn rows<T: DeserializeOwned>(sheet: impl Read) -> Result<Vec<(u64, T)>, ImportError> {
+    let mut reader = Reader::from_reader(sheet);
+    let headers = reader.headers()?.clone();
+    let mut record = StringRecord::new();
+    let mut rows = Vec::new();
+    while reader.read_record(&mut record)? {
+        let line = record.position().map_or(0, Position::line);
+        rows.push((line, record.deserialize(Some(&headers))?));
+    }
+    Ok(rows)
+}
+
+/// Parse a time, like `08:40` or `08:40:00`.
+fn parse_time(line: u64, time: &str) -> Result<NaiveTime, ImportError> {
+    let time = time.trim();
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+        .map_err(|_| ImportError::InvalidTime(line, time.to_string()))
+}
+
+/// Create a bell from a row of the bells sheet.
+fn create_bell(
+    line: u64,
+    row: BellRow,
+    subjects: &HashMap<String, Uuid>,
+    unknown: &HashMap<Uuid, UnknownFields>,
+) -> Result<BellTime, ImportError> {
+    let bell_data = match row.kind.trim().to_ascii_lowercase().as_str() {
+        "" => None,
+        "class" => Some(BellData::Class {
+            subject_id: *subjects
+                .get(&row.subject)
+                .ok_or_else(|| ImportError::SubjectNotFound(line, row.subject.clone()))?,
+            location: row.location,
+        }),
+        "time" => Some(BellData::Time),
+        "break" => Some(BellData::Break),
+        "study" => Some(BellData::Study),
+        "pause" => Some(BellData::Pause),
+        _ => Some(BellData::Other {
+            name: row.kind.trim().to_string(),
+        }),
+    };
+    Ok(BellTime {
+        id: row.id.unwrap_or_default(),
+        name: row.name,
+        time: parse_time(line, &row.time)?,
+        bell_data,
+        enabled: row.enabled.unwrap_or(true),
+        icon: row.icon.filter(|icon| !icon.trim().is_empty()),
+        unknown: row
+            .id
+            .and_then(|id| unknown.get(&id).cloned())
+            .unwrap_or_default(),
+    })
+}
+
+/// Import bells and subjects from two CSV sheets, in the layout described in
+/// [`crate::export::csv::to_csv`], into existing data.
+///
+/// The subjects are replaced by the rows of the subjects sheet, and classes refer to them by name.
+/// Subjects without an `id` are given a random UUID.
+///
+/// Week variants are matched to the `variant` column by name, and their bells are replaced by the
+/// rows naming them, in order. Week variants which aren't named keep no bells, and new names are
+/// added as cyclical week variants. Bells without an `id` are given a stable UUID with
+/// [`crate::school::School::assign_ids`], bells without `enabled` are enabled, and bells without `icon` have
+/// the icon of their type. Bells and subjects with the `id` of an existing one keep its unknown
+/// fields. Everything else about the data is kept.
+///
+/// # Errors
+///
+/// This function will return an error if a sheet can't be read ([`ImportError::CsvError`]), if a
+/// time can't be parsed, if a class refers to a subject which doesn't exist, or if subjects or
+/// week variants have the same name.
+pub fn from_csv(
+    mut data: Subjective,
+    bells: impl Read,
+    subjects: impl Read,
+) -> Result<Subjective, ImportError> {
+    let subject_unknown = data
+        .subjects
+        .drain(..)
+        .map(|subject| (subject.id, subject.unknown))
+        .collect::<HashMap<_, _>>();
+    let mut subject_ids = HashMap::new();
+    data.subjects = rows::<SubjectRow>(subjects)?
+        .into_iter()
+        .map(|(_, row)| {
+            let id = row.id.unwrap_or_else(Uuid::new_v4);
+            if subject_ids.insert(row.name.clone(), id).is_some() {
+                return Err(ImportError::DuplicateSubject(row.name));
+            }
+            Ok(Subject {
+                id,
+                name: row.name,
+                color: Color {
+                    red: row.red,
+                    green: row.green,
+                    blue: row.blue,
+                },
+                locations: row
+                    .locations
+                    .split(LOCATION_SEPARATOR)
+                    .map(str::trim)
+                    .filter(|location| !location.is_empty())
+                    .map(ToString::to_string)
+                    .collect(),
+                icon: row.icon,
+                unknown: subject_unknown.get(&id).cloned().unwrap_or_default(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let school = &mut data.school;
+    let mut variants = HashSet::new();
+    if let Some(week) = school
+        .bell_times
+        .iter()
+        .find(|week| !variants.insert(&week.name))
+    {
+        return Err(ImportError::DuplicateVariant(week.name.clone()));
+    }
+    let bell_unknown = school
+        .bell_times
+        .iter()
+        .flat_map(|week| week.days.bells())
+        .filter(|bell| !bell.id.is_nil())
+        .map(|bell| (bell.id, bell.unknown.clone()))
+        .collect::<HashMap<_, _>>();
+    for week in &mut school.bell_times {
+        week.days = Days::default();
+    }
+    for (line, row) in rows::<BellRow>(bells)? {
+        let index = if let Some(index) = school
+            .bell_times
+            .iter()
+            .position(|week| week.name == row.variant)
+        {
+            index
+        } else {
+            school.bell_times.push(Week {
+                id: Uuid::nil(),
+                name: row.variant.clone(),
+                days: Days::default(),
+                cyclical: true,
+                unknown: UnknownFields::new(),
+            });
+            school.bell_times.len() - 1
+        };
+        let weekday = row.weekday;
+        let bell = create_bell(line, row, &subject_ids, &bell_unknown)?;
+        school.bell_times[index].days[weekday].push(bell);
+    }
+    school.assign_ids();
+    Ok(data)
+}
@@ -0,0 +1,532 @@
+#![cfg(feature = "std")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Weekday};
+use chrono_tz::Tz;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    SEARCH_DAYS, Subjective,
+    color::Color,
+    school::{
+        Day, Days, School, Week,
+        bells::{BellData, BellTime},
+        cycle::CycleAnchor,
+    },
+    subjects::Subject,
+    unknown::UnknownFields,
+};
+
+/// Longest cycle of week variants which is inferred by [`from_ical`].
+pub const MAX_CYCLE_WEEKS: i64 = 4;
+
+/// Errors that can occur when importing a timetable with [`from_ical`].
+#[derive(Error, Debug)]
+pub enum ImportError {
+    /// A date or time couldn't be parsed.
+    #[error("Couldn't parse the date or time \"{0}\".")]
+    InvalidDateTime(String),
+    /// There are no events with a start and end time.
+    #[error("No events with a start and end time were found.")]
+    NoEvents,
+}
+
+/// Property of an iCalendar component, like `DTSTART;TZID=Australia/Sydney:20260126T090000`.
+struct Property<'a> {
+    name: String,
+    parameters: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl<'a> Property<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let (head, value) = line.split_once(':')?;
+        let mut head = head.split(';');
+        let name = head.next()?.to_ascii_uppercase();
+        let parameters = head
+            .filter_map(|parameter| parameter.split_once('='))
+            .map(|(key, value)| (key.to_ascii_uppercase(), value.trim_matches('"')))
+            .collect();
+        Some(Self {
+            name,
+            parameters,
+            value,
+        })
+    }
+
+    fn parameter(&self, key: &str) -> Option<&'a str> {
+        self.parameters
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Unfold content lines, as described in RFC 5545.
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Unescape text from a property value, as described in RFC 5545.
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut characters = text.chars();
+    while let Some(character) = characters.next() {
+        if character == '\\' {
+            match characters.next() {
+                Some('n' | 'N') => output.push('\n'),
+                Some(escaped) => output.push(escaped),
+                None => {}
+            }
+        } else {
+            output.push(character);
+        }
+    }
+    output
+}
+
+/// Parse a date-time into the local time of the school.
+/// Returns [`None`] for dates without a time, like all-day events.
+fn parse_date_time(
+    property: &Property,
+    timezone: Option<Tz>,
+) -> Result<Option<NaiveDateTime>, ImportError> {
+    let invalid = || ImportError::InvalidDateTime(property.value.to_string());
+    if property.parameter("VALUE") == Some("DATE") || !property.value.contains('T') {
+        return Ok(None);
+    }
+    if let Some(utc) = property.value.strip_suffix('Z') {
+        let date_time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .map_err(|_| invalid())?
+            .and_utc();
+        return Ok(Some(timezone.map_or_else(
+            || date_time.naive_utc(),
+            |timezone| date_time.with_timezone(&timezone).naive_local(),
+        )));
+    }
+    let date_time =
+        NaiveDateTime::parse_from_str(property.value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    let event_timezone = property
+        .parameter("TZID")
+        .and_then(|timezone| timezone.parse::<Tz>().ok());
+    Ok(Some(match (event_timezone, timezone) {
+        (Some(event_timezone), Some(timezone)) if event_timezone != timezone => event_timezone
+            .from_local_datetime(&date_time)
+            .earliest()
+            .map_or(date_time, |date_time| {
+                date_time.with_timezone(&timezone).naive_local()
+            }),
+        _ => date_time,
+    }))
+}
+
+/// Parse the `BYDAY` value of a weekday, as described in RFC 5545.
+fn parse_weekday(weekday: &str) -> Option<Weekday> {
+    Some(match weekday.get(weekday.len().checked_sub(2)?..)? {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// Class taken from an event, which takes place on a single date.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Class {
+    start: NaiveTime,
+    end: NaiveTime,
+    summary: String,
+    location: String,
+}
+
+#[derive(Default)]
+struct Event {
+    uid: String,
+    start: Option<NaiveDateTime>,
+    end: Option<NaiveDateTime>,
+    duration: Option<TimeDelta>,
+    summary: String,
+    location: String,
+    rule: Option<String>,
+    exceptions: Vec<NaiveDateTime>,
+    /// Whether the event is an instance of a recurring event with the same `UID`, which replaces
+    /// the occurrence starting at its `RECURRENCE-ID`.
+    instance: bool,
+    recurrence_id: Option<NaiveDateTime>,
+    cancelled: bool,
+}
+
+impl Event {
+    /// Expand the event into the dates it takes place on, following weekly `RRULE`s.
+    fn occurrences(&self) -> Vec<(NaiveDate, Class)> {
+        let Some(start) = self.start else {
+            return Vec::new();
+        };
+        let Some(end) = self.end.or_else(|| Some(start + self.duration?)) else {
+            return Vec::new();
+        };
+        if end.date() != start.date() || end <= start {
+            return Vec::new();
+        }
+        let class = Class {
+            start: start.time(),
+            end: end.time(),
+            summary: self.summary.clone(),
+            location: self.location.clone(),
+        };
+        let mut dates = vec![start.date()];
+        if let Some(rule) = &self.rule {
+            let parts = rule
+                .split(';')
+                .filter_map(|part| part.split_once('='))
+                .collect::<HashMap<_, _>>();
+            if parts.get("FREQ") == Some(&"WEEKLY") {
+                let interval = parts
+                    .get("INTERVAL")
+                    .and_then(|interval| interval.parse().ok())
+                    .unwrap_or(1_i64)
+                    .max(1);
+                let count = parts.get("COUNT").and_then(|count| count.parse().ok());
+                let until = parts
+                    .get("UNTIL")
+                    .and_then(|until| NaiveDate::parse_from_str(until.get(..8)?, "%Y%m%d").ok());
+                let weekdays = parts.get("BYDAY").map_or_else(
+                    || vec![start.weekday()],
+                    |weekdays| weekdays.split(',').filter_map(parse_weekday).collect(),
+                );
+                let monday = start.date().week(Weekday::Mon).first_day();
+                dates = monday
+                    .iter_days()
+                    .take(SEARCH_DAYS)
+                    .filter(|date| {
+                        (*date - monday).num_weeks() % interval == 0
+                            && weekdays.contains(&date.weekday())
+                            && *date >= start.date()
+                    })
+                    .take_while(|date| until.is_none_or(|until| *date <= until))
+                    .take(count.unwrap_or(usize::MAX))
+                    .collect();
+            }
+        }
+        dates
+            .into_iter()
+            .filter(|date| !self.exceptions.contains(&date.and_time(start.time())))
+            .map(|date| (date, class.clone()))
+            .collect()
+    }
+}
+
+/// Number of conflicting days if the weeks are split into a cycle of the given number of weeks.
+fn conflicts(days: &BTreeMap<(i64, u32), Vec<Class>>, cycle: i64) -> usize {
+    let mut templates: HashMap<(i64, u32), &Vec<Class>> = HashMap::new();
+    days.iter()
+        .filter(|((week, weekday), classes)| {
+            let template = templates
+                .entry((week.rem_euclid(cycle), *weekday))
+                .or_insert(classes);
+            template != classes
+        })
+        .count()
+}
+
+/// Convert the classes of a day into bells, adding a break or end bell wherever a class ends
+/// without another class starting.
+fn bells(classes: &[Class], subjects: &[Subject]) -> Day {
+    let mut bells = Vec::new();
+    let mut periods = 0;
+    let mut breaks = 0;
+    for (index, class) in classes.iter().enumerate() {
+        periods += 1;
+        bells.push(BellTime {
+            id: Uuid::nil(),
+            name: format!("Period {periods}"),
+            time: class.start,
+            bell_data: subjects
+                .iter()
+                .find(|subject| subject.name == class.summary)
+                .map(|subject| BellData::Class {
+                    subject_id: subject.id,
+                    location: class.location.clone(),
+                }),
+            enabled: true,
+            icon: None,
+            unknown: UnknownFields::new(),
+        });
+        match classes.get(index + 1) {
+            Some(next) if next.start <= class.end => {}
+            Some(_) => {
+                breaks += 1;
+                bells.push(BellTime {
+                    id: Uuid::nil(),
+                    name: format!("Break {breaks}"),
+                    time: class.end,
+                    bell_data: Some(BellData::Break),
+                    enabled: true,
+                    icon: None,
+                    unknown: UnknownFields::new(),
+                });
+            }
+            None => bells.push(BellTime {
+                id: Uuid::nil(),
+                name: "End".to_string(),
+                time: class.end,
+                bell_data: Some(BellData::Time),
+                enabled: true,
+                icon: None,
+                unknown: UnknownFields::new(),
+            }),
+        }
+    }
+    bells
+}
+
+/// Parse the `VEVENT`s of a calendar, ignoring the properties of components nested in them, like
+/// `VALARM`s.
+///
+/// Occurrences of recurring events which are replaced by an instance with a `RECURRENCE-ID` are
+/// removed, and cancelled events are left out.
+fn parse_events(properties: &[Property], timezone: Option<Tz>) -> Result<Vec<Event>, ImportError> {
+    let mut events = Vec::new();
+    let mut event: Option<Event> = None;
+    let mut depth = 0_usize;
+    for property in properties {
+        match (property.name.as_str(), property.value, &mut event) {
+            ("BEGIN", "VEVENT", _) => {
+                event = Some(Event::default());
+                depth = 0;
+            }
+            ("BEGIN", _, Some(_)) => depth += 1,
+            ("END", "VEVENT", Some(_)) if depth == 0 => events.extend(event.take()),
+            ("END", _, Some(_)) => depth = depth.saturating_sub(1),
+            (_, _, Some(_)) if depth > 0 => {}
+            ("UID", value, Some(event)) => event.uid = value.to_string(),
+            ("DTSTART", _, Some(event)) => event.start = parse_date_time(property, timezone)?,
+            ("DTEND", _, Some(event)) => event.end = parse_date_time(property, timezone)?,
+            ("DURATION", value, Some(event)) => event.duration = parse_duration(value),
+            ("SUMMARY", value, Some(event)) => event.summary = unescape(value),
+            ("LOCATION", value, Some(event)) => event.location = unescape(value),
+            ("RRULE", value, Some(event)) => event.rule = Some(value.to_string()),
+            ("STATUS", value, Some(event)) => {
+                event.cancelled = value.eq_ignore_ascii_case("CANCELLED");
+            }
+            ("RECURRENCE-ID", _, Some(event)) => {
+                event.instance = true;
+                event.recurrence_id = parse_date_time(property, timezone)?;
+            }
+            ("EXDATE", value, Some(event)) => {
+                for exception in value.split(',') {
+                    let exception = Property {
+                        name: property.name.clone(),
+                        parameters: property.parameters.clone(),
+                        value: exception,
+                    };
+                    event
+                        .exceptions
+                        .extend(parse_date_time(&exception, timezone)?);
+                }
+            }
+            _ => {}
+        }
+    }
+    let replaced = events
+        .iter()
+        .filter_map(|event| Some((event.uid.clone(), event.recurrence_id?)))
+        .collect::<Vec<_>>();
+    for event in events.iter_mut().filter(|event| !event.instance) {
+        event.exceptions.extend(
+            replaced
+                .iter()
+                .filter(|(uid, _)| *uid == event.uid)
+                .map(|(_, recurrence_id)| *recurrence_id),
+        );
+    }
+    events.retain(|event| !event.cancelled);
+    Ok(events)
+}
+
+/// Create a subject for each distinct summary of the classes, with the locations it is taught in.
+fn create_subjects(days: &BTreeMap<(i64, u32), Vec<Class>>) -> Vec<Subject> {
+    let mut summaries = days
+        .values()
+        .flatten()
+        .map(|class| class.summary.clone())
+        .collect::<Vec<_>>();
+    summaries.sort();
+    summaries.dedup();
+    summaries
+        .into_iter()
+        .enumerate()
+        .map(|(index, summary)| {
+            let mut locations = days
+                .values()
+                .flatten()
+                .filter(|class| class.summary == summary && !class.location.is_empty())
+                .map(|class| class.location.clone())
+                .collect::<Vec<_>>();
+            locations.sort();
+            locations.dedup();
+            Subject {
+                id: Uuid::new_v4(),
+                name: summary,
+                color: Color::generated(index),
+                locations,
+                icon: "book.fill".to_string(),
+                unknown: UnknownFields::new(),
+            }
+        })
+        .collect()
+}
+
+/// Create the week variants of a cycle, taking each day from the first week with its bells.
+fn create_weeks(
+    days: &BTreeMap<(i64, u32), Vec<Class>>,
+    cycle: i64,
+    subjects: &[Subject],
+) -> Vec<Week> {
+    (0..cycle)
+        .map(|variant| {
+            let mut week_days = Days::default();
+            for (weekday, day) in week_days.iter_mut() {
+                if let Some(classes) = days
+                    .iter()
+                    .find(|((week, day_index), _)| {
+                        week.rem_euclid(cycle) == variant
+                            && *day_index == weekday.num_days_from_monday()
+                    })
+                    .map(|(_, classes)| classes)
+                {
+                    *day = bells(classes, subjects);
+                }
+            }
+            Week {
+                id: Uuid::nil(),
+                name: if cycle == 1 {
+                    "Week".to_string()
+                } else {
+                    format!(
+                        "Week {}",
+                        char::from(b'A' + u8::try_from(variant).unwrap_or(0))
+                    )
+                },
+                days: week_days,
+                cyclical: true,
+                unknown: UnknownFields::new(),
+            }
+        })
+        .collect()
+}
+
+/// Import a timetable from an iCalendar (`.ics`) file.
+///
+/// Each timed event becomes a class, with a [`Subject`] for each distinct `SUMMARY`, and the
+/// `LOCATION` as the classroom. Weekly `RRULE`s are expanded, occurrences which were moved are taken
+/// from the instance with their `RECURRENCE-ID`, and cancelled events are skipped. The events are
+/// split into week variants by finding the shortest cycle, of up to [`MAX_CYCLE_WEEKS`] weeks, in
+/// which every day repeats. The cycle is anchored to the first week with events.
+///
+/// Where a class ends without another class starting, a break bell is added, and the last class
+/// of each day is followed by an end bell.
+/// Week variants and bells are given stable UUIDs with [`School::assign_ids`].
+///
+/// # Errors
+///
+/// This function will return an error if a date or time can't be parsed
+/// ([`ImportError::InvalidDateTime`]), or if there are no events with a start and end time
+/// ([`ImportError::NoEvents`]).
+pub fn from_ical(ics: &str) -> Result<Subjective, ImportError> {
+    let lines = unfold(ics);
+    let properties = lines
+        .iter()
+        .filter_map(|line| Property::parse(line))
+        .collect::<Vec<_>>();
+    let mut name = "Imported Timetable".to_string();
+    let mut timezone = None;
+    for property in &properties {
+        match property.name.as_str() {
+            "X-WR-CALNAME" => name = unescape(property.value),
+            "X-WR-TIMEZONE" => timezone = property.value.parse::<Tz>().ok(),
+            "DTSTART" if timezone.is_none() => {
+                timezone = property
+                    .parameter("TZID")
+                    .and_then(|timezone| timezone.parse().ok());
+            }
+            _ => {}
+        }
+    }
+    let events = parse_events(&properties, timezone)?;
+    let occurrences = events
+        .iter()
+        .flat_map(Event::occurrences)
+        .collect::<Vec<_>>();
+    let first_monday = occurrences
+        .iter()
+        .map(|(date, _)| date.week(Weekday::Mon).first_day())
+        .min()
+        .ok_or(ImportError::NoEvents)?;
+    let mut days: BTreeMap<(i64, u32), Vec<Class>> = BTreeMap::new();
+    for (date, class) in occurrences {
+        let week = (date - first_monday).num_weeks();
+        days.entry((week, date.weekday().num_days_from_monday()))
+            .or_default()
+            .push(class);
+    }
+    for classes in days.values_mut() {
+        classes.sort();
+        classes.dedup();
+    }
+    let cycle = (1..=MAX_CYCLE_WEEKS)
+        .min_by_key(|cycle| (conflicts(&days, *cycle), *cycle))
+        .unwrap_or(1);
+    let subjects = create_subjects(&days);
+    let bell_times = create_weeks(&days, cycle, &subjects);
+    let mut school = School {
+        bell_times,
+        cycle_anchor: (cycle > 1).then_some(CycleAnchor {
+            date: first_monday,
+            variant: 0,
+        }),
+        timezone: timezone.map(|timezone| timezone.name().to_string()),
+        ..School::new(name)
+    };
+    school.assign_ids();
+    Ok(Subjective::new(school, subjects))
+}
+
+/// Parse a duration, like `PT1H30M`, as described in RFC 5545.
+fn parse_duration(duration: &str) -> Option<TimeDelta> {
+    let duration = duration.strip_prefix('+').unwrap_or(duration);
+    let mut total = TimeDelta::zero();
+    let mut number = String::new();
+    for character in duration.strip_prefix('P')?.chars() {
+        match character {
+            '0'..='9' => number.push(character),
+            'T' => {}
+            unit => {
+                let value = number.parse::<i64>().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => TimeDelta::weeks(value),
+                    'D' => TimeDelta::days(value),
+                    'H' => TimeDelta::hours(value),
+                    'M' => TimeDelta::minutes(value),
+                    'S' => TimeDelta::seconds(value),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(total)
+}
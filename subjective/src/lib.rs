@@ -20,25 +20,84 @@ use std::{
 };
 
 use cfg_if::cfg_if;
-use chrono::{Datelike, NaiveDate, NaiveDateTime};
-use school::{Day, School, bells::BellTime};
+#[cfg(feature = "std")]
+use chrono::{DateTime, TimeDelta, TimeZone};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Weekday};
+#[cfg(feature = "std")]
+use migration::MigrationError;
+#[cfg(feature = "std")]
+use school::overrides::{DayOverride, Replacement};
+use school::{Day, School, bells::BellTime, period::Period};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "std")]
-use serde_json::{Error, from_str};
+use serde_json::Error;
 #[cfg(feature = "std")]
 use subjects::Subject;
+#[cfg(feature = "std")]
+use unknown::UnknownFields;
+/// Compact binary format of timetables, which can be read without an allocator.
+pub mod binary;
+/// Generation of Rust code which embeds timetables as `no_std` data, for build scripts.
+pub mod codegen;
 /// Colors used for subjects.
 pub mod color;
+/// Export of Subjective data to other formats.
+pub mod export;
+/// Import of Subjective data from other formats.
+pub mod import;
+/// Migration of Subjective data from older versions of the data format.
+pub mod migration;
+/// Normalization of Subjective data.
+pub mod normalization;
+/// Rendering of timetables as weekly grids, in HTML and Markdown.
+pub mod render;
+/// JSON Schemas of Subjective data formats.
+pub mod schema;
 /// School related structures.
 pub mod school;
+/// Human-authorable TOML source format, compiled into Subjective data.
+pub mod source;
 /// Subject related structures.
 pub mod subjects;
+/// Preservation of fields which aren't known to this version of the library.
+pub mod unknown;
+/// Validation of Subjective data.
+pub mod validation;
 
 use thiserror::Error;
 #[cfg(feature = "std")]
 use uuid::Uuid;
 
+pub use chrono;
+
+/// Include a timetable as a `no_std` [`Subjective`], which can be the value of a `static`, so that
+/// it can be used without a parser or an allocator.
+///
+/// The code must be generated by calling `subjective::codegen::generate` with the same file from
+/// a build script, using a build dependency on this library with the `std` feature.
+///
+/// # Examples
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     subjective::codegen::generate("school.subjective").unwrap();
+/// }
+///
+/// // src/main.rs
+/// use subjective::Subjective;
+///
+/// static TIMETABLE: Subjective<'static, 'static, 'static> =
+///     subjective::include_timetable!("school.subjective");
+/// ```
+#[macro_export]
+macro_rules! include_timetable {
+    ($file:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/", $file, ".rs"))
+    };
+}
+
 #[cfg(feature = "std")]
 /// Errors that can occur when loading Subjective data.
 #[derive(Error, Debug)]
@@ -51,34 +110,45 @@ pub enum LoadDataError {
     DataFileReadError(io::Error),
     /// The Subjective data file could not be parsed.
     #[error(
-        "Failed to parse Subjective data file. This may be due to invalid or outdated data. Try re-exporting your data again.\n{0}"
+        "Failed to parse Subjective data file. This may be due to invalid data. Try re-exporting your data again.\n{0}"
     )]
     DataFileParseError(Error),
+    /// The Subjective data file could not be migrated to the current version of the data format.
+    #[error("Failed to migrate Subjective data file to the current version.\n{0}")]
+    MigrationError(#[from] MigrationError),
 }
 
 /// Errors that can occur when retrieving bells.
 #[derive(Error, Debug)]
 pub enum FindBellError {
-    /// The specified weekday is out of range.
-    #[error("The ISO 8601 weekday number {0} is out of the range `1..=5`.")]
-    WeekdayOutOfRange(usize),
     /// No bell was found.
     #[error("No bell was found.")]
     NoBellFound,
+    /// No week variant was found, because none are cyclical or activated, or the requested variant
+    /// doesn't exist.
+    #[error(
+        "No week variant was found. Check that a week variant is cyclical or activated, and that the requested variant exists."
+    )]
+    NoVariantFound,
 }
 
+/// Number of days searched by [`Subjective::bells_from`] and [`Subjective::bells_before`] before
+/// giving up, so that timetables without any bells don't search forever.
+pub const SEARCH_DAYS: usize = 366;
+
 macro_rules! subjective_impl_inner {
     () => {
         /// The name of the Subjective data file.
         pub const CONFIG_FILE: &'static str = ".subjective";
 
         #[cfg(feature = "std")]
-        /// Load Subjective data from a config directory.
+        /// Load Subjective data from a config directory, migrating it to the current version of the
+        /// data format with [`Subjective::from_json`].
         ///
         /// # Errors
         ///
         /// This function will return an error if the data file is not found, cannot be read, or cannot
-        /// be parsed.
+        /// be parsed or migrated.
         pub fn from_config(config_directory: &Path) -> Result<Self, LoadDataError> {
             let timetable_path = config_directory.join(Self::CONFIG_FILE);
             let mut timetable = File::open(timetable_path.clone())
@@ -87,7 +157,7 @@ macro_rules! subjective_impl_inner {
             timetable
                 .read_to_string(&mut raw)
                 .map_err(LoadDataError::DataFileReadError)?;
-            let data = from_str(&raw).map_err(LoadDataError::DataFileParseError)?;
+            let (data, _) = Self::from_json(&raw)?;
             Ok(data)
         }
 
@@ -95,7 +165,11 @@ macro_rules! subjective_impl_inner {
         #[must_use]
         /// Create a new Subjective data structure.
         pub const fn new(school: School, subjects: Vec<Subject>) -> Self {
-            Self { school, subjects }
+            Self {
+                school,
+                subjects,
+                unknown: UnknownFields::new(),
+            }
         }
 
         #[cfg(feature = "std")]
@@ -105,6 +179,7 @@ macro_rules! subjective_impl_inner {
             Self {
                 school,
                 subjects: Vec::new(),
+                unknown: UnknownFields::new(),
             }
         }
 
@@ -116,16 +191,18 @@ macro_rules! subjective_impl_inner {
         ///
         /// # Errors
         ///
-        /// This function will return an error if the weekday is out of range
-        /// ([`FindBellError::WeekdayOutOfRange`]).
-        /// If no bells are found, because there are no bell times after the given time for the
-        /// specified day, it returns ([`FindBellError::NoBellFound`]).
+        /// This function will return an error if no week variant is active on the given date
+        /// ([`FindBellError::NoVariantFound`]).
+        /// If no bells are found, because there is no school or no bell times after the given time for
+        /// the specified day, it returns ([`FindBellError::NoBellFound`]).
         pub fn find_all_after(
             &self,
             date_time: NaiveDateTime,
             variant_offset: usize,
         ) -> Result<&[BellTime], FindBellError> {
-            let day = self.get_day(date_time.date(), variant_offset)?;
+            let day = self
+                .get_day(date_time.date(), variant_offset)?
+                .ok_or(FindBellError::NoBellFound)?;
             let time = date_time.time();
             let bells = day
                 .iter()
@@ -146,16 +223,18 @@ macro_rules! subjective_impl_inner {
         ///
         /// # Errors
         ///
-        /// This function will return an error if the weekday is out of range
-        /// ([`FindBellError::WeekdayOutOfRange`]).
-        /// If no bells are found, because there are no bell times before the given time for the
-        /// specified day, it returns ([`FindBellError::NoBellFound`]).
+        /// This function will return an error if no week variant is active on the given date
+        /// ([`FindBellError::NoVariantFound`]).
+        /// If no bells are found, because there is no school or no bell times before the given time
+        /// for the specified day, it returns ([`FindBellError::NoBellFound`]).
         pub fn find_all_before(
             &self,
             date_time: NaiveDateTime,
             variant_offset: usize,
         ) -> Result<&[BellTime], FindBellError> {
-            let day = self.get_day(date_time.date(), variant_offset)?;
+            let day = self
+                .get_day(date_time.date(), variant_offset)?
+                .ok_or(FindBellError::NoBellFound)?;
             let time = date_time.time();
             let bells = day
                 .iter()
@@ -174,16 +253,18 @@ macro_rules! subjective_impl_inner {
         ///
         /// # Errors
         ///
-        /// This function will return an error if the weekday is out of range
-        /// ([`FindBellError::WeekdayOutOfRange`]).
-        /// If no bell is found, because there are no bell times after the given time for the specified
-        /// day, it returns ([`FindBellError::NoBellFound`]).
+        /// This function will return an error if no week variant is active on the given date
+        /// ([`FindBellError::NoVariantFound`]).
+        /// If no bell is found, because there is no school or no bell times after the given time for the
+        /// specified day, it returns ([`FindBellError::NoBellFound`]).
         pub fn find_first_after(
             &self,
             date_time: NaiveDateTime,
             variant_offset: usize,
         ) -> Result<&BellTime, FindBellError> {
-            let day = self.get_day(date_time.date(), variant_offset)?;
+            let day = self
+                .get_day(date_time.date(), variant_offset)?
+                .ok_or(FindBellError::NoBellFound)?;
             let time = date_time.time();
             day.iter()
                 .find(|bell| bell.time >= time && bell.enabled)
@@ -196,16 +277,18 @@ macro_rules! subjective_impl_inner {
         ///
         /// # Errors
         ///
-        /// This function will return an error if the weekday is out of range
-        /// ([`FindBellError::WeekdayOutOfRange`]).
-        /// If no bell is found, because there are no bell times before the given time for the specified
-        /// day, it returns ([`FindBellError::NoBellFound`]).
+        /// This function will return an error if no week variant is active on the given date
+        /// ([`FindBellError::NoVariantFound`]).
+        /// If no bell is found, because there is no school or no bell times before the given time for
+        /// the specified day, it returns ([`FindBellError::NoBellFound`]).
         pub fn find_first_before(
             &self,
             date_time: NaiveDateTime,
             variant_offset: usize,
         ) -> Result<&BellTime, FindBellError> {
-            let day = self.get_day(date_time.date(), variant_offset)?;
+            let day = self
+                .get_day(date_time.date(), variant_offset)?
+                .ok_or(FindBellError::NoBellFound)?;
             let time = date_time.time();
             day.iter()
                 .rev()
@@ -213,50 +296,279 @@ macro_rules! subjective_impl_inner {
                 .ok_or(FindBellError::NoBellFound)
         }
 
+        /// Find the period in progress at a given time, on a specified weekday.
+        /// Periods span from one enabled bell to the next, so there is no period in progress before
+        /// the first bell or after the last bell of the day.
+        /// Bells must be sorted in ascending order for this function to work correctly.
+        ///
+        /// # Errors
+        ///
+        /// This function will return an error if no week variant is active on the given date
+        /// ([`FindBellError::NoVariantFound`]).
+        /// If no period is in progress at the given time, including when there is no school, it
+        /// returns ([`FindBellError::NoBellFound`]).
+        pub fn current_period(
+            &self,
+            date_time: NaiveDateTime,
+            variant_offset: usize,
+        ) -> Result<Period<'_>, FindBellError> {
+            let day = self
+                .get_day(date_time.date(), variant_offset)?
+                .ok_or(FindBellError::NoBellFound)?;
+            let time = date_time.time();
+            Period::from_day(day)
+                .find(|period| period.contains(time))
+                .ok_or(FindBellError::NoBellFound)
+        }
+
+        /// Iterate over all enabled bells at or after a given time, in ascending order.
+        /// Unlike [`Self::find_all_after`], searches are continued over days, weekends, and week
+        /// variant boundaries, yielding each bell with the date it rings on.
+        /// Days without school, or which cannot be retrieved with [`Self::get_day`], are skipped.
+        /// The search ends [`SEARCH_DAYS`] days after the given date.
+        /// Bells must be sorted in ascending order for this function to work correctly.
+        pub fn bells_from(
+            &self,
+            date_time: NaiveDateTime,
+            variant_offset: usize,
+        ) -> impl Iterator<Item = (NaiveDate, &BellTime)> + '_ {
+            let start = date_time.date();
+            let time = date_time.time();
+            start
+                .iter_days()
+                .take(SEARCH_DAYS)
+                .flat_map(move |date| {
+                    self.get_day(date, variant_offset)
+                        .ok()
+                        .flatten()
+                        .into_iter()
+                        .flat_map(|day| day.iter())
+                        .filter(move |bell| bell.enabled && (date != start || bell.time >= time))
+                        .map(move |bell| (date, bell))
+                })
+        }
+
+        /// Iterate over all enabled bells at or before a given time, in descending order.
+        /// Unlike [`Self::find_all_before`], searches are continued over days, weekends, and week
+        /// variant boundaries, yielding each bell with the date it rang on.
+        /// Days without school, or which cannot be retrieved with [`Self::get_day`], are skipped.
+        /// The search ends [`SEARCH_DAYS`] days before the given date.
+        /// Bells must be sorted in ascending order for this function to work correctly.
+        pub fn bells_before(
+            &self,
+            date_time: NaiveDateTime,
+            variant_offset: usize,
+        ) -> impl Iterator<Item = (NaiveDate, &BellTime)> + '_ {
+            let start = date_time.date();
+            let time = date_time.time();
+            start
+                .iter_days()
+                .rev()
+                .take(SEARCH_DAYS)
+                .flat_map(move |date| {
+                    self.get_day(date, variant_offset)
+                        .ok()
+                        .flatten()
+                        .into_iter()
+                        .flat_map(|day| day.iter().rev())
+                        .filter(move |bell| bell.enabled && (date != start || bell.time <= time))
+                        .map(move |bell| (date, bell))
+                })
+        }
+
+        /// Get the index of the week variant active on a given date.
+        /// Explicitly activated variants take precedence, otherwise the variant is chosen from the
+        /// cyclical week variants.
+        /// If the school has a [`school::cycle::CycleAnchor`], the variant is counted in weeks from
+        /// the anchor, plus `variant_offset`. Otherwise, it is calculated using
+        ///
+        /// `current_variant = (week_number + variant_offset) % cyclical_weeks`.
+        ///
+        /// Returns [`None`] if no variant is activated and no week variant is cyclical, or if the
+        /// cycle anchor doesn't refer to a cyclical week variant.
+        #[must_use]
+        pub fn current_variant(&self, date: NaiveDate, variant_offset: usize) -> Option<usize> {
+            #[cfg(feature = "std")]
+            if let Some(activation) = self
+                .school
+                .variant_activations
+                .iter()
+                .rev()
+                .find(|activation| {
+                    activation.contains(date) && activation.variant < self.school.bell_times.len()
+                })
+            {
+                return Some(activation.variant);
+            }
+            let cyclical = || {
+                self.school
+                    .bell_times
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, week)| week.cyclical)
+                    .map(|(variant, _)| variant)
+            };
+            let weeks = cyclical().count();
+            if weeks == 0 {
+                return None;
+            }
+            let current_variant = match self.school.cycle_anchor {
+                Some(anchor) => {
+                    let anchor_variant =
+                        cyclical().position(|variant| variant == anchor.variant)?;
+                    get_anchored_variant(
+                        anchor.weeks_until(date),
+                        anchor_variant,
+                        variant_offset,
+                        weeks,
+                    )
+                }
+                None => get_current_variant(date, variant_offset, weeks),
+            };
+            cyclical().nth(current_variant)
+        }
+
         cfg_if! {
             if #[cfg(feature = "std")] {
-                /// Get the day for a given date, calculating the current variant using
+                /// Get the day for a given date.
+                /// If the school has a [`school::overrides::DayOverride`] for the date, its replacement is
+                /// used. Otherwise, the day is taken from the variant given by [`Self::current_variant`].
+                /// Returns [`None`] if there is no school on the given date, because the day has no bells
+                /// or the school's calendar says so.
+                ///
+                /// # Errors
                 ///
-                /// `current_variant = (week_number + variant_offset) % weeks`.
+                /// This function will return an error if no week variant is active on the given date
+                /// ([`FindBellError::NoVariantFound`]).
+                pub fn get_day(&self, date: NaiveDate, variant_offset: usize) -> Result<Option<&Day>, FindBellError> {
+                    if let Some(day_override) = self.get_override(date) {
+                        return match &day_override.replacement {
+                            Replacement::Bells { bells } => Ok((!bells.is_empty()).then_some(bells)),
+                            Replacement::Weekday { variant, weekday } => {
+                                let variant = match variant {
+                                    Some(variant) => *variant,
+                                    None => self
+                                        .current_variant(date, variant_offset)
+                                        .ok_or(FindBellError::NoVariantFound)?,
+                                };
+                                self.get_variant_day(variant, *weekday)
+                            }
+                        };
+                    }
+                    if !self.school.calendar.is_school_day(date) {
+                        return Ok(None);
+                    }
+                    let current_variant = self
+                        .current_variant(date, variant_offset)
+                        .ok_or(FindBellError::NoVariantFound)?;
+                    self.get_variant_day(current_variant, date.weekday())
+                }
+
+                /// Get the day for a given weekday, in a given week variant.
+                /// Returns [`None`] if there is no school on the weekday, because it has no bells.
                 ///
                 /// # Errors
                 ///
-                /// This function will return an error if the weekday is out of range
-                /// ([`FindBellError::WeekdayOutOfRange`]).
-                #[allow(clippy::cast_sign_loss)]
-                pub fn get_day(&self, date: NaiveDate, variant_offset: usize) -> Result<&Day, FindBellError> {
-                    let weekday = date.weekday().num_days_from_monday() as usize;
-                    let current_variant =
-                        get_current_variant(date, variant_offset, self.school.bell_times.len());
-                    let bell_times = &self.school.bell_times[current_variant].days;
-                    let day = bell_times
-                        .get(weekday)
-                        .ok_or(FindBellError::WeekdayOutOfRange(weekday))?;
-                    Ok(day)
+                /// This function will return an error if the variant is out of range
+                /// ([`FindBellError::NoVariantFound`]).
+                pub fn get_variant_day(&self, variant: usize, weekday: Weekday) -> Result<Option<&Day>, FindBellError> {
+                    let day = self
+                        .school
+                        .bell_times
+                        .get(variant)
+                        .ok_or(FindBellError::NoVariantFound)?
+                        .days
+                        .get(weekday);
+                    Ok((!day.is_empty()).then_some(day))
+                }
+
+                #[must_use]
+                /// Get the [`school::overrides::DayOverride`] for a given date, if there is one.
+                /// Later overrides take precedence over earlier ones.
+                pub fn get_override(&self, date: NaiveDate) -> Option<&DayOverride> {
+                    self.school
+                        .overrides
+                        .iter()
+                        .rev()
+                        .find(|day_override| day_override.date == date)
                 }
             } else {
-                /// Get the day for a given date, calculating the current variant using
+                /// Get the day for a given date, in the variant given by [`Self::current_variant`].
+                /// Returns [`None`] if there is no school on the given date, because the day has no bells.
                 ///
-                /// `current_variant = (week_number + variant_offset) % weeks`.
+                /// # Errors
+                ///
+                /// This function will return an error if no week variant is active on the given date
+                /// ([`FindBellError::NoVariantFound`]).
+                pub fn get_day(&self, date: NaiveDate, variant_offset: usize) -> Result<Option<&Day<'_>>, FindBellError> {
+                    let current_variant = self
+                        .current_variant(date, variant_offset)
+                        .ok_or(FindBellError::NoVariantFound)?;
+                    self.get_variant_day(current_variant, date.weekday())
+                }
+
+                /// Get the day for a given weekday, in a given week variant.
+                /// Returns [`None`] if there is no school on the weekday, because it has no bells or is
+                /// after the last day of the week variant.
                 ///
                 /// # Errors
                 ///
-                /// This function will return an error if the weekday is out of range
-                /// ([`FindBellError::WeekdayOutOfRange`]).
-                #[allow(clippy::cast_sign_loss)]
-                pub fn get_day(&self, date: NaiveDate, variant_offset: usize) -> Result<&Day<'_>, FindBellError> {
-                    let weekday = date.weekday().num_days_from_monday() as usize;
-                    let current_variant =
-                        get_current_variant(date, variant_offset, self.school.bell_times.len());
-                    let bell_times = &self.school.bell_times[current_variant].days;
-                    let day = bell_times
-                        .get(weekday)
-                        .ok_or(FindBellError::WeekdayOutOfRange(weekday))?;
-                    Ok(day)
+                /// This function will return an error if the variant is out of range
+                /// ([`FindBellError::NoVariantFound`]).
+                pub fn get_variant_day(&self, variant: usize, weekday: Weekday) -> Result<Option<&Day<'_>>, FindBellError> {
+                    Ok(self
+                        .school
+                        .bell_times
+                        .get(variant)
+                        .ok_or(FindBellError::NoVariantFound)?
+                        .days
+                        .get(weekday.num_days_from_monday() as usize)
+                        .filter(|day| !day.is_empty()))
                 }
             }
         }
 
+        #[cfg(feature = "std")]
+        #[must_use]
+        /// Convert a time to the local time of the school, using the school's timezone.
+        /// If the school has no timezone, the local time of the given time is used.
+        pub fn school_time<Tz: TimeZone>(&self, date_time: &DateTime<Tz>) -> NaiveDateTime {
+            self.school.tz().map_or_else(
+                || date_time.naive_local(),
+                |timezone| date_time.with_timezone(&timezone).naive_local(),
+            )
+        }
+
+        #[cfg(feature = "std")]
+        #[must_use]
+        /// Convert a local time of the school to an instant in the school's timezone.
+        /// If the local time is ambiguous, because clocks are turned back for daylight saving time,
+        /// the earliest instant is used. If the local time is skipped, because clocks are turned
+        /// forward, the instant an hour later is used.
+        /// Returns [`None`] if the school has no timezone.
+        pub fn localize(&self, date_time: NaiveDateTime) -> Option<DateTime<chrono_tz::Tz>> {
+            let timezone = self.school.tz()?;
+            timezone
+                .from_local_datetime(&date_time)
+                .earliest()
+                .or_else(|| {
+                    timezone
+                        .from_local_datetime(&(date_time + TimeDelta::hours(1)))
+                        .earliest()
+                })
+        }
+
+        #[cfg(feature = "std")]
+        #[must_use]
+        /// Get the duration between two local times of the school, taking daylight saving time
+        /// transitions into account if the school has a timezone.
+        pub fn duration_between(&self, from: NaiveDateTime, to: NaiveDateTime) -> TimeDelta {
+            match (self.localize(from), self.localize(to)) {
+                (Some(from), Some(to)) => to - from,
+                _ => to - from,
+            }
+        }
+
         #[cfg(feature = "std")]
         #[must_use]
         /// Get the subject with the given ID.
@@ -275,12 +587,16 @@ macro_rules! subjective_impl_inner {
 cfg_if! {
     if #[cfg(feature = "std")] {
         #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         /// Structure of a Subjective data file.
         pub struct Subjective {
             /// School data.
             pub school: School,
             /// Subject data.
             pub subjects: Vec<Subject>,
+            /// Unknown fields, kept so that they are saved unchanged.
+            #[serde(flatten)]
+            pub unknown: UnknownFields,
         }
 
         impl Subjective {
@@ -303,6 +619,7 @@ cfg_if! {
 
 
 /// Get the current variant for a given date, variant offset, and number of variants.
+/// The variant is an index into the variants included in the automatic cycle.
 #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
 #[must_use]
 pub fn get_current_variant(date: NaiveDate, variant_offset: usize, variants: usize) -> usize {
@@ -310,3 +627,23 @@ pub fn get_current_variant(date: NaiveDate, variant_offset: usize, variants: usi
     let week_number = date.iso_week().week() as usize;
     (week_number + variant_offset) % weeks
 }
+
+/// Get the current variant for a given number of weeks since a [`school::cycle::CycleAnchor`].
+///
+/// `anchor_variant` is the variant of the anchored week.
+/// The variants are indices into the variants included in the automatic cycle.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+#[must_use]
+pub const fn get_anchored_variant(
+    weeks_since_anchor: i64,
+    anchor_variant: usize,
+    variant_offset: usize,
+    variants: usize,
+) -> usize {
+    (weeks_since_anchor + anchor_variant as i64 + variant_offset as i64).rem_euclid(variants as i64)
+        as usize
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "std")]
+
+use core::fmt::{self, Display, Formatter};
+
+use serde_json::{Map, Value, from_value, json};
+use thiserror::Error;
+
+use crate::{LoadDataError, Subjective, school::School};
+
+/// Version of the data format which is used by this version of the library.
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Errors that can occur when migrating Subjective data with [`migrate_school`].
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    /// The data isn't a JSON object.
+    #[error("The data isn't a JSON object.")]
+    NotAnObject,
+    /// The data doesn't have the layout of its version.
+    #[error("The data doesn't have the layout of version {0}: {1}")]
+    InvalidLayout(u32, &'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Migration of Subjective data from one version of the data format to the next, which was run by
+/// [`migrate_school`].
+pub struct Migration {
+    /// Version migrated from.
+    pub from: u32,
+    /// Version migrated to.
+    pub to: u32,
+    /// What the migration changed.
+    pub description: &'static str,
+}
+
+impl Display for Migration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Migrated from version {} to {}: {}",
+            self.from, self.to, self.description
+        )
+    }
+}
+
+/// Migration and the function which runs it on the school data, returning whether the data was
+/// changed.
+type Step = (
+    Migration,
+    fn(&mut Map<String, Value>) -> Result<bool, MigrationError>,
+);
+
+/// Migrations, in order. Each migrates from the version before its `to` version.
+const MIGRATIONS: &[Step] = &[(
+    Migration {
+        from: 1,
+        to: 2,
+        description: "Moved the bells into a week variant.",
+    },
+    migrate_v1,
+)];
+
+/// Version 1 stored one week of bells, as an array of days, instead of week variants.
+///
+/// Data which is labelled version 1 but already has week variants is left unchanged.
+fn migrate_v1(school: &mut Map<String, Value>) -> Result<bool, MigrationError> {
+    let days = school
+        .remove("bellTimes")
+        .ok_or(MigrationError::InvalidLayout(1, "There are no bell times."))?;
+    if !matches!(days.get(0), Some(Value::Array(_))) {
+        school.insert("bellTimes".to_string(), days);
+        return Ok(false);
+    }
+    school.insert(
+        "bellTimes".to_string(),
+        Value::Array(vec![json!({
+            "name": "Week",
+            "days": days,
+            "cyclical": true,
+        })]),
+    );
+    Ok(true)
+}
+
+/// Detect the version of the data format of school data from its layout: version 1 if the first
+/// item of `bellTimes` is an array of bells rather than a week variant.
+fn layout_version(school: &Map<String, Value>) -> u32 {
+    match school
+        .get("bellTimes")
+        .and_then(Value::as_array)
+        .and_then(|weeks| weeks.first())
+    {
+        Some(Value::Array(_)) => 1,
+        _ => CURRENT_VERSION,
+    }
+}
+
+/// Detect the version of the data format of school data.
+///
+/// The version is taken from the `version` field. Data without one, or with one which isn't a
+/// whole number, like `1.0`, is detected from its layout.
+///
+/// # Errors
+///
+/// This function will return an error if the data isn't an object.
+pub fn detect_version(school: &Value) -> Result<u32, MigrationError> {
+    let school = school.as_object().ok_or(MigrationError::NotAnObject)?;
+    let version = match school.get("version") {
+        Some(Value::String(version)) => version.trim().parse().ok(),
+        Some(Value::Number(version)) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok()),
+        _ => None,
+    };
+    Ok(version.unwrap_or_else(|| layout_version(school)))
+}
+
+/// Migrate school data to the current version of the data format, [`CURRENT_VERSION`].
+///
+/// Migrations are run in order, from the detected version of the data, and the `version` field is
+/// updated after each one. Data from newer versions is left unchanged, as it can still be loaded
+/// with its unknown fields kept.
+/// Returns the migrations which changed the data, which is empty if the data was already current.
+///
+/// # Errors
+///
+/// This function will return an error if the data isn't an object, or if it doesn't have the
+/// layout of its version.
+pub fn migrate_school(school: &mut Value) -> Result<Vec<Migration>, MigrationError> {
+    let version = detect_version(school)?;
+    let school = school.as_object_mut().ok_or(MigrationError::NotAnObject)?;
+    let mut migrations = Vec::new();
+    for (migration, run) in MIGRATIONS
+        .iter()
+        .filter(|(migration, _)| migration.from >= version)
+    {
+        let changed = run(school)?;
+        school.insert(
+            "version".to_string(),
+            Value::String(migration.to.to_string()),
+        );
+        if changed {
+            migrations.push(*migration);
+        }
+    }
+    Ok(migrations)
+}
+
+impl Subjective {
+    /// Load Subjective data from JSON, migrating it to the current version of the data format, and
+    /// assigning missing UUIDs with [`School::assign_ids`]. Returns the data with the migrations
+    /// which were run.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the data can't be parsed or migrated.
+    pub fn from_json(json: &str) -> Result<(Self, Vec<Migration>), LoadDataError> {
+        let mut value: Value =
+            serde_json::from_str(json).map_err(LoadDataError::DataFileParseError)?;
+        let migrations = match value.get_mut("school") {
+            Some(school) => migrate_school(school)?,
+            None => Vec::new(),
+        };
+        let mut data: Self = from_value(value).map_err(LoadDataError::DataFileParseError)?;
+        data.school.assign_ids();
+        Ok((data, migrations))
+    }
+}
+
+impl School {
+    /// Load a catalog of schools from JSON, migrating each to the current version of the data
+    /// format and assigning missing UUIDs. Returns the schools with the migrations which were run.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the catalog can't be parsed or migrated.
+    pub fn catalog_from_json(json: &str) -> Result<(Vec<Self>, Vec<Migration>), LoadDataError> {
+        let mut schools: Vec<Value> =
+            serde_json::from_str(json).map_err(LoadDataError::DataFileParseError)?;
+        let mut migrations = Vec::new();
+        for school in &mut schools {
+            migrations.extend(migrate_school(school)?);
+        }
+        let mut schools: Vec<Self> =
+            from_value(Value::Array(schools)).map_err(LoadDataError::DataFileParseError)?;
+        for school in &mut schools {
+            school.assign_ids();
+        }
+        Ok((schools, migrations))
+    }
+}
@@ -0,0 +1,256 @@
+#![cfg(feature = "std")]
+
+use core::fmt::{self, Display, Formatter};
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveTime};
+use uuid::Uuid;
+
+use crate::{
+    Subjective,
+    school::{Day, School, overrides::Replacement},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Kind of change made to Subjective data by [`Subjective::normalize`] or
+/// [`School::assign_ids`].
+pub enum ChangeKind {
+    /// The bells of a day were sorted into ascending order.
+    SortedBells,
+    /// A bell identical to an earlier bell of the same day was removed.
+    RemovedDuplicateBell(NaiveTime),
+    /// A missing or duplicated UUID was replaced with a stable UUID.
+    AssignedId(Uuid),
+    /// An override was removed, because a later override is on the same date.
+    RemovedOverride,
+    /// A week variant activation was removed, because it is never active.
+    RemovedActivation,
+    /// A holiday was removed, because it ends before it starts.
+    RemovedHoliday,
+    /// A pupil-free day was removed, because an earlier pupil-free day is on the same date.
+    RemovedPupilFreeDay,
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SortedBells => write!(f, "Sorted bells into ascending order."),
+            Self::RemovedDuplicateBell(time) => {
+                write!(f, "Removed duplicate bell at {}.", time.format("%-I:%M %p"))
+            }
+            Self::AssignedId(id) => write!(f, "Assigned UUID \"{id}\"."),
+            Self::RemovedOverride => {
+                write!(f, "Removed override replaced by a later override.")
+            }
+            Self::RemovedActivation => write!(f, "Removed activation which is never active."),
+            Self::RemovedHoliday => write!(f, "Removed holiday which ends before it starts."),
+            Self::RemovedPupilFreeDay => write!(f, "Removed duplicate pupil-free day."),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Change made to Subjective data by [`Subjective::normalize`] or [`School::assign_ids`], with
+/// where it was made.
+pub struct Change {
+    /// Where the change was made, like `Week "Week A", Mon`.
+    pub location: String,
+    /// The change which was made.
+    pub kind: ChangeKind,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.kind)
+    }
+}
+
+/// Create a UUID from a key, which is the same every time it is created from the same key.
+pub(crate) fn stable_id(key: &str) -> Uuid {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+    let hash = key.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+    });
+    Uuid::new_v8(hash.to_be_bytes())
+}
+
+#[derive(Default)]
+struct Changes(Vec<Change>);
+
+impl Changes {
+    fn push(&mut self, location: impl Into<String>, kind: ChangeKind) {
+        self.0.push(Change {
+            location: location.into(),
+            kind,
+        });
+    }
+
+    fn assign_id(&mut self, location: &str, id: &mut Uuid, key: &str, used: &mut HashSet<Uuid>) {
+        if !id.is_nil() && used.insert(*id) {
+            return;
+        }
+        *id = (0..usize::MAX)
+            .map(|attempt| stable_id(&format!("{key}/{attempt}")))
+            .find(|id| used.insert(*id))
+            .unwrap_or_default();
+        self.push(location, ChangeKind::AssignedId(*id));
+    }
+
+    fn normalize_day(&mut self, location: &str, day: &mut Day) {
+        if !day.is_sorted_by_key(|bell| bell.time) {
+            day.sort_by_key(|bell| bell.time);
+            self.push(location, ChangeKind::SortedBells);
+        }
+        let mut kept: Day = Vec::with_capacity(day.len());
+        for bell in day.drain(..) {
+            if kept.contains(&bell) {
+                self.push(location, ChangeKind::RemovedDuplicateBell(bell.time));
+            } else {
+                kept.push(bell);
+            }
+        }
+        *day = kept;
+    }
+
+    fn assign_bell_ids(
+        &mut self,
+        location: &str,
+        day: &mut Day,
+        key: &str,
+        used: &mut HashSet<Uuid>,
+    ) {
+        for bell in day {
+            let key = format!("{key}/{}/{}", bell.time, bell.name);
+            self.assign_id(
+                &format!("{location}, bell \"{}\"", bell.name),
+                &mut bell.id,
+                &key,
+                used,
+            );
+        }
+    }
+}
+
+impl School {
+    /// Replace missing or duplicated UUIDs of week variants, bells, and links with UUIDs derived
+    /// from their contents, so the same data is always given the same UUIDs.
+    ///
+    /// This is done when data is loaded with [`Subjective::from_json`] or imported, so that UUIDs
+    /// are always saved. Returns the changes which were made.
+    pub fn assign_ids(&mut self) -> Vec<Change> {
+        let mut changes = Changes::default();
+        let mut used_weeks = HashSet::new();
+        let mut used_bells = HashSet::new();
+        for week in &mut self.bell_times {
+            let location = format!("Week \"{}\"", week.name);
+            changes.assign_id(
+                &location,
+                &mut week.id,
+                &format!("week/{}", week.name),
+                &mut used_weeks,
+            );
+            for (weekday, day) in week.days.iter_mut() {
+                changes.assign_bell_ids(
+                    &format!("{location}, {weekday}"),
+                    day,
+                    &format!("bell/{}/{weekday}", week.id),
+                    &mut used_bells,
+                );
+            }
+        }
+        for day_override in &mut self.overrides {
+            if let Replacement::Bells { bells } = &mut day_override.replacement {
+                changes.assign_bell_ids(
+                    &format!("Override on {}", day_override.date),
+                    bells,
+                    &format!("bell/{}", day_override.date),
+                    &mut used_bells,
+                );
+            }
+        }
+        let mut used_links = HashSet::new();
+        for link in &mut self.links {
+            changes.assign_id(
+                &format!("Link \"{}\"", link.name),
+                &mut link.id,
+                &format!("link/{}/{}", link.name, link.destination),
+                &mut used_links,
+            );
+        }
+        changes.0
+    }
+}
+
+impl Subjective {
+    /// Repair the data, so that it can be used reliably and compared between loads.
+    ///
+    /// Bells are sorted into ascending order and identical bells in the same day are removed.
+    /// Missing or duplicated UUIDs are then replaced, as described in [`School::assign_ids`].
+    /// Overrides, activations, holidays, and pupil-free days which can never take effect are
+    /// removed.
+    /// Returns the changes which were made, which is empty if the data was already normalized.
+    pub fn normalize(&mut self) -> Vec<Change> {
+        let mut changes = Changes::default();
+        let school = &mut self.school;
+        let weeks = school.bell_times.len();
+        for week in &mut school.bell_times {
+            let location = format!("Week \"{}\"", week.name);
+            for (weekday, day) in week.days.iter_mut() {
+                changes.normalize_day(&format!("{location}, {weekday}"), day);
+            }
+        }
+        let mut override_dates = HashSet::new();
+        let mut overrides = Vec::with_capacity(school.overrides.len());
+        for day_override in school.overrides.drain(..).rev() {
+            if override_dates.insert(day_override.date) {
+                overrides.push(day_override);
+            } else {
+                changes.push(
+                    format!("Override on {}", day_override.date),
+                    ChangeKind::RemovedOverride,
+                );
+            }
+        }
+        overrides.reverse();
+        school.overrides = overrides;
+        for day_override in &mut school.overrides {
+            if let Replacement::Bells { bells } = &mut day_override.replacement {
+                changes.normalize_day(&format!("Override on {}", day_override.date), bells);
+            }
+        }
+        school.variant_activations.retain(|activation| {
+            let active = activation.variant < weeks && activation.start <= activation.end;
+            if !active {
+                changes.push(
+                    format!("Activation from {}", activation.start),
+                    ChangeKind::RemovedActivation,
+                );
+            }
+            active
+        });
+        school.calendar.holidays.retain(|holiday| {
+            let valid = holiday.start <= holiday.end;
+            if !valid {
+                changes.push(
+                    format!("Holiday \"{}\"", holiday.name),
+                    ChangeKind::RemovedHoliday,
+                );
+            }
+            valid
+        });
+        let mut pupil_free_dates: HashSet<NaiveDate> = HashSet::new();
+        school.calendar.pupil_free_days.retain(|day| {
+            let reachable = pupil_free_dates.insert(day.date);
+            if !reachable {
+                changes.push(
+                    format!("Pupil-free day \"{}\"", day.name),
+                    ChangeKind::RemovedPupilFreeDay,
+                );
+            }
+            reachable
+        });
+        changes.0.extend(school.assign_ids());
+        changes.0
+    }
+}
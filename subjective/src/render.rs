@@ -0,0 +1,179 @@
+#![cfg(feature = "std")]
+
+use core::fmt;
+
+use chrono::{NaiveTime, Weekday};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    Subjective,
+    school::{
+        Week,
+        bells::{BellData, BellTime},
+    },
+    subjects::Subject,
+};
+
+/// HTML rendering of timetables, for printing.
+pub mod html;
+/// Markdown rendering of timetables, for notes apps.
+pub mod markdown;
+
+/// Errors that can occur when rendering a timetable.
+#[derive(Error, Debug)]
+pub enum RenderError {
+    /// The subject with the given ID was not found. This means that the data is invalid.
+    #[error("No subject found matching \"{0}\". This means that your Subjective data is invalid.")]
+    SubjectNotFound(Uuid),
+    /// An error occurred while writing the timetable.
+    #[error(transparent)]
+    FmtError(#[from] fmt::Error),
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Bell in a [`Grid`], with the subject and location of classes.
+pub struct Cell<'a> {
+    /// The bell.
+    pub bell: &'a BellTime,
+    /// Time of the next enabled bell, which ends the period started by an enabled bell.
+    pub end: Option<NaiveTime>,
+    /// Subject of a class.
+    pub subject: Option<&'a Subject>,
+    /// Location of a class, or an empty string.
+    pub location: &'a str,
+}
+
+impl<'a> Cell<'a> {
+    /// Create the cells of the bells of a day, in order.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a class refers to a subject which doesn't exist
+    /// ([`RenderError::SubjectNotFound`]).
+    pub fn from_day(data: &'a Subjective, day: &'a [BellTime]) -> Result<Vec<Self>, RenderError> {
+        day.iter()
+            .enumerate()
+            .map(|(index, bell)| {
+                let end = day[index + 1..]
+                    .iter()
+                    .find(|next| next.enabled)
+                    .filter(|_| bell.enabled)
+                    .map(|next| next.time);
+                let (subject, location) = match &bell.bell_data {
+                    Some(BellData::Class {
+                        subject_id,
+                        location,
+                    }) => (
+                        Some(
+                            data.get_subject(*subject_id)
+                                .ok_or(RenderError::SubjectNotFound(*subject_id))?,
+                        ),
+                        location.as_str(),
+                    ),
+                    _ => (None, ""),
+                };
+                Ok(Self {
+                    bell,
+                    end,
+                    subject,
+                    location,
+                })
+            })
+            .collect()
+    }
+
+    /// Get the title of the cell, which is the name of the subject of a class, or the name of the
+    /// bell otherwise.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        self.subject
+            .map_or(self.bell.name.as_str(), |subject| subject.name.as_str())
+    }
+
+    /// Get the time of the cell, like `8:40 AM – 9:40 AM`, or `3:00 PM` if it doesn't start a
+    /// period.
+    #[must_use]
+    pub fn time(&self) -> String {
+        let start = self.bell.time.format("%-I:%M %p");
+        self.end.map_or_else(
+            || start.to_string(),
+            |end| format!("{start} – {}", end.format("%-I:%M %p")),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Weekly grid of the bells of a week variant, with a column per weekday and a row per bell,
+/// in order.
+pub struct Grid<'a> {
+    /// The week variant.
+    pub week: &'a Week,
+    /// Weekdays of the columns, which are Monday to Friday, and the weekend if it has bells.
+    pub weekdays: Vec<Weekday>,
+    /// Rows of cells, with a cell per weekday. Days with fewer bells have empty cells at the end.
+    pub rows: Vec<Vec<Option<Cell<'a>>>>,
+}
+
+impl<'a> Grid<'a> {
+    /// Create the grid of a week variant.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a class refers to a subject which doesn't exist
+    /// ([`RenderError::SubjectNotFound`]).
+    pub fn new(data: &'a Subjective, week: &'a Week) -> Result<Self, RenderError> {
+        let weekdays = week
+            .days
+            .iter()
+            .filter(|(weekday, day)| {
+                !matches!(weekday, Weekday::Sat | Weekday::Sun) || !day.is_empty()
+            })
+            .map(|(weekday, _)| weekday)
+            .collect::<Vec<_>>();
+        let columns = weekdays
+            .iter()
+            .map(|weekday| Cell::from_day(data, &week.days[*weekday]))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows = (0..columns.iter().map(Vec::len).max().unwrap_or(0))
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| column.get(row).copied())
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            week,
+            weekdays,
+            rows,
+        })
+    }
+
+    /// Create the grids of every week variant of a timetable.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a class refers to a subject which doesn't exist
+    /// ([`RenderError::SubjectNotFound`]).
+    pub fn all(data: &'a Subjective) -> Result<Vec<Self>, RenderError> {
+        data.school
+            .bell_times
+            .iter()
+            .map(|week| Self::new(data, week))
+            .collect()
+    }
+}
+
+/// Get the full name of a weekday, like `Monday`.
+pub(crate) const fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
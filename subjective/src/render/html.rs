@@ -0,0 +1,108 @@
+use core::fmt::Write;
+
+use crate::{
+    Subjective,
+    color::Color,
+    render::{Cell, Grid, RenderError, weekday_name},
+};
+
+/// Styles of rendered timetables, which fit each week variant on a printed page.
+const STYLE: &str = "body { font-family: system-ui, sans-serif; margin: 2em; }
+section { break-inside: avoid; margin-bottom: 2em; }
+table { border-collapse: collapse; table-layout: fixed; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 0.4em; text-align: left; vertical-align: top; }
+td.class { background: color-mix(in srgb, var(--color) 20%, white); border-left: 0.3em solid var(--color); }
+td.disabled { opacity: 0.5; }
+td span, td time { display: block; font-size: 0.85em; color: #555; }
+@media print { body { margin: 0; } section { break-after: page; } }";
+
+/// Escape text for use in HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Get a color as a CSS color, like `rgb(66 126 128)`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn css_color(color: Color) -> String {
+    format!(
+        "rgb({} {} {})",
+        (color.red * 255_f32) as u8,
+        (color.green * 255_f32) as u8,
+        (color.blue * 255_f32) as u8
+    )
+}
+
+fn write_cell(output: &mut String, cell: &Cell) -> Result<(), RenderError> {
+    let disabled = if cell.bell.enabled { "" } else { " disabled" };
+    match cell.subject {
+        Some(subject) => write!(
+            output,
+            "<td class=\"class{disabled}\" style=\"--color: {}\">",
+            css_color(subject.color)
+        )?,
+        None => write!(output, "<td class=\"bell{disabled}\">")?,
+    }
+    write!(output, "<strong>{}</strong>", escape(cell.title()))?;
+    if cell.subject.is_some() {
+        let mut details = escape(&cell.bell.name);
+        if !cell.location.is_empty() {
+            write!(details, " · {}", escape(cell.location))?;
+        }
+        write!(output, "<span>{details}</span>")?;
+    }
+    write!(output, "<time>{}</time></td>", cell.time())?;
+    Ok(())
+}
+
+/// Render the timetable as an HTML page, with a weekly grid per week variant, for printing.
+///
+/// Each column is a weekday and each row is a bell, in order. Classes show the subject, the bell
+/// name, and the location, and are colored with the subject's color. Disabled bells are faded.
+///
+/// # Errors
+///
+/// This function will return an error if a class refers to a subject which doesn't exist
+/// ([`RenderError::SubjectNotFound`]).
+pub fn to_html(data: &Subjective) -> Result<String, RenderError> {
+    let name = escape(&data.school.name);
+    let mut output = String::new();
+    writeln!(output, "<!DOCTYPE html>")?;
+    writeln!(output, "<html>")?;
+    writeln!(output, "<head>")?;
+    writeln!(output, "<meta charset=\"utf-8\">")?;
+    writeln!(output, "<title>{name}</title>")?;
+    writeln!(output, "<style>\n{STYLE}\n</style>")?;
+    writeln!(output, "</head>")?;
+    writeln!(output, "<body>")?;
+    writeln!(output, "<h1>{name}</h1>")?;
+    for grid in Grid::all(data)? {
+        writeln!(output, "<section>")?;
+        writeln!(output, "<h2>{}</h2>", escape(&grid.week.name))?;
+        writeln!(output, "<table>")?;
+        write!(output, "<thead><tr>")?;
+        for weekday in &grid.weekdays {
+            write!(output, "<th>{}</th>", weekday_name(*weekday))?;
+        }
+        writeln!(output, "</tr></thead>")?;
+        writeln!(output, "<tbody>")?;
+        for row in &grid.rows {
+            write!(output, "<tr>")?;
+            for cell in row {
+                match cell {
+                    Some(cell) => write_cell(&mut output, cell)?,
+                    None => write!(output, "<td></td>")?,
+                }
+            }
+            writeln!(output, "</tr>")?;
+        }
+        writeln!(output, "</tbody>")?;
+        writeln!(output, "</table>")?;
+        writeln!(output, "</section>")?;
+    }
+    writeln!(output, "</body>")?;
+    writeln!(output, "</html>")?;
+    Ok(output)
+}
@@ -0,0 +1,73 @@
+use core::fmt::Write;
+
+use crate::{
+    Subjective,
+    render::{Cell, Grid, RenderError, weekday_name},
+};
+
+/// Escape text for use in a Markdown table cell.
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, character| {
+        if matches!(
+            character,
+            '\\' | '|' | '*' | '_' | '`' | '~' | '[' | ']' | '<'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+        escaped
+    })
+}
+
+fn cell(cell: &Cell) -> String {
+    let mut text = format!("**{}**", escape(cell.title()));
+    if cell.subject.is_some() {
+        text.push_str("<br>");
+        text.push_str(&escape(&cell.bell.name));
+        if !cell.location.is_empty() {
+            text.push_str(" · ");
+            text.push_str(&escape(cell.location));
+        }
+    }
+    text.push_str("<br>");
+    text.push_str(&cell.time());
+    if cell.bell.enabled {
+        text
+    } else {
+        format!("~~{text}~~")
+    }
+}
+
+/// Render the timetable as Markdown, with a table per week variant, for notes apps.
+///
+/// Each column is a weekday and each row is a bell, in order. Classes show the subject, the bell
+/// name, and the location. Disabled bells are struck through.
+///
+/// # Errors
+///
+/// This function will return an error if a class refers to a subject which doesn't exist
+/// ([`RenderError::SubjectNotFound`]).
+pub fn to_markdown(data: &Subjective) -> Result<String, RenderError> {
+    let mut output = String::new();
+    writeln!(output, "# {}", escape(&data.school.name))?;
+    for grid in Grid::all(data)? {
+        writeln!(output)?;
+        writeln!(output, "## {}", escape(&grid.week.name))?;
+        writeln!(output)?;
+        let header = grid
+            .weekdays
+            .iter()
+            .map(|weekday| weekday_name(*weekday))
+            .collect::<Vec<_>>();
+        writeln!(output, "| {} |", header.join(" | "))?;
+        writeln!(output, "|{}", " --- |".repeat(header.len()))?;
+        for row in &grid.rows {
+            let cells = row
+                .iter()
+                .map(|cell| cell.as_ref().map(self::cell).unwrap_or_default())
+                .collect::<Vec<_>>();
+            writeln!(output, "| {} |", cells.join(" | "))?;
+        }
+    }
+    Ok(output)
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "schema")]
+
+use schemars::{Schema, schema_for};
+
+use crate::{Subjective, school::School};
+
+/// Generate the JSON Schema of Subjective data files, like `.subjective` files.
+#[must_use]
+pub fn data_schema() -> Schema {
+    schema_for!(Subjective)
+}
+
+/// Generate the JSON Schema of `SubjectiveKit` catalogs, like `schools.json`, which are lists of
+/// schools.
+#[must_use]
+pub fn catalog_schema() -> Schema {
+    let mut schema = schema_for!(Vec<School>);
+    schema.insert("title".to_string(), "SubjectiveKit catalog".into());
+    schema.insert(
+        "description".to_string(),
+        "Schools which can be pulled from SubjectiveKit.".into(),
+    );
+    schema
+}
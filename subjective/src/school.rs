@@ -1,46 +1,78 @@
+/// Week variant activation data.
+pub mod activation;
 /// Bell-related data.
 pub mod bells;
+/// School calendar data, including terms, holidays, and pupil-free days.
+pub mod calendar;
+/// Week cycle data.
+pub mod cycle;
 /// Link-related data.
 pub mod link;
 /// Notice-related data.
 pub mod notice;
+/// Date-specific schedule override data.
+pub mod overrides;
+/// Period-related data.
+pub mod period;
 
-use crate::school::bells::BellTime;
 #[cfg(feature = "std")]
-use crate::school::{link::Link, notice::Notice};
+use crate::school::{
+    activation::VariantActivation, calendar::Calendar, link::Link, notice::Notice,
+    overrides::DayOverride,
+};
+use crate::school::{bells::BellTime, cycle::CycleAnchor};
+#[cfg(feature = "std")]
+use crate::unknown::UnknownFields;
 use cfg_if::cfg_if;
 #[cfg(feature = "std")]
+use chrono::Weekday;
+#[cfg(feature = "std")]
+use chrono_tz::Tz;
+#[cfg(feature = "std")]
 use colored::Colorize;
 #[cfg(feature = "std")]
 use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use core::{
+    hash::{Hash, Hasher},
+    ops::{Index, IndexMut},
+};
 #[cfg(all(feature = "diff", feature = "std"))]
-use diff::{Diff, VecDiff};
+use diff::Diff;
 #[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
 #[cfg(feature = "std")]
 use uuid::Uuid;
 
 cfg_if! {
     if #[cfg(feature = "std")] {
         #[derive(Debug, Clone, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         /// A week variant of a Subjective timetable.
         pub struct Week {
-            #[serde(default = "Uuid::new_v4")]
-            /// UUID of the week variant.
+            #[serde(default)]
+            /// UUID of the week variant. If the data doesn't have one, it is nil until assigned by
+            /// [`School::assign_ids`], which is done when data is loaded or imported.
             pub id: Uuid,
             /// Name of the week variant.
             pub name: String,
             /// Days of the week.
-            pub days: Vec<Day>,
+            pub days: Days,
             /// Whether the week variant is included in the automatic cycle.
             pub cyclical: bool,
+            /// Unknown fields, kept so that they are saved unchanged.
+            #[serde(flatten)]
+            pub unknown: UnknownFields,
         }
     } else {
         #[derive(Debug, Clone, Default, Hash)]
         /// A week variant of a Subjective timetable.
         pub struct Week<'a, 'b> {
-            /// Days of the week.
+            /// Days of the week, starting on Monday. Days after the end of the slice have no
+            /// school.
             pub days: &'a [Day<'b>],
+            /// Whether the week variant is included in the automatic cycle.
+            pub cyclical: bool,
         }
     }
 }
@@ -54,9 +86,11 @@ pub struct WeekDiff {
     /// Differences in the name of the week.
     pub name: Option<String>,
     /// Differences in the days of the week.
-    pub days: VecDiff<Day>,
+    pub days: DaysDiff,
     /// Differences in the cyclical status of the week.
     pub cyclical: Option<bool>,
+    /// Differences in the unknown fields of the week.
+    pub unknown: Option<UnknownFields>,
 }
 
 #[cfg(all(feature = "diff", feature = "std"))]
@@ -73,6 +107,7 @@ impl Diff for Week {
             name: self.name.diff(&other.name),
             days: self.days.diff(&other.days),
             cyclical: self.cyclical.diff(&other.cyclical),
+            unknown: self.unknown.diff(&other.unknown),
         }
     }
 
@@ -83,18 +118,32 @@ impl Diff for Week {
         self.name.apply(&diff.name);
         self.days.apply(&diff.days);
         self.cyclical.apply(&diff.cyclical);
+        self.unknown.apply(&diff.unknown);
     }
 
     fn identity() -> Self {
         Self {
             id: Uuid::nil(),
             name: String::new(),
-            days: Vec::new(),
+            days: Days::default(),
             cyclical: false,
+            unknown: UnknownFields::new(),
         }
     }
 }
 
+#[cfg(feature = "std")]
+/// Weekdays in the order that [`Days`] are serialised in.
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
 cfg_if! {
     if #[cfg(feature = "std")] {
         /// A day of the week, containing bell times for each period.
@@ -106,9 +155,237 @@ cfg_if! {
     }
 }
 
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+/// Days of a [`Week`], keyed by weekday. Days without bells have no school.
+///
+/// Days are serialised as an array starting on Monday, like the Subjective app does. Saturday and
+/// Sunday are left out of the array if they have no bells.
+pub struct Days {
+    /// Bells on Monday.
+    pub monday: Day,
+    /// Bells on Tuesday.
+    pub tuesday: Day,
+    /// Bells on Wednesday.
+    pub wednesday: Day,
+    /// Bells on Thursday.
+    pub thursday: Day,
+    /// Bells on Friday.
+    pub friday: Day,
+    /// Bells on Saturday.
+    pub saturday: Day,
+    /// Bells on Sunday.
+    pub sunday: Day,
+    /// Number of days in the array the days were loaded from, or [`None`] if they weren't loaded.
+    /// Missing days are loaded as days without school, so this is kept to report truncated data
+    /// in [`crate::Subjective::validate`]. It isn't compared, diffed, or saved.
+    pub(crate) loaded_length: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl Days {
+    /// Get the bells on a weekday.
+    #[must_use]
+    pub const fn get(&self, weekday: Weekday) -> &Day {
+        match weekday {
+            Weekday::Mon => &self.monday,
+            Weekday::Tue => &self.tuesday,
+            Weekday::Wed => &self.wednesday,
+            Weekday::Thu => &self.thursday,
+            Weekday::Fri => &self.friday,
+            Weekday::Sat => &self.saturday,
+            Weekday::Sun => &self.sunday,
+        }
+    }
+
+    /// Get the bells on a weekday mutably.
+    #[must_use]
+    pub const fn get_mut(&mut self, weekday: Weekday) -> &mut Day {
+        match weekday {
+            Weekday::Mon => &mut self.monday,
+            Weekday::Tue => &mut self.tuesday,
+            Weekday::Wed => &mut self.wednesday,
+            Weekday::Thu => &mut self.thursday,
+            Weekday::Fri => &mut self.friday,
+            Weekday::Sat => &mut self.saturday,
+            Weekday::Sun => &mut self.sunday,
+        }
+    }
+
+    /// Iterate over the days with their weekdays, starting on Monday.
+    pub fn iter(&self) -> impl Iterator<Item = (Weekday, &Day)> {
+        WEEKDAYS
+            .into_iter()
+            .map(|weekday| (weekday, self.get(weekday)))
+    }
+
+    /// Iterate mutably over the days with their weekdays, starting on Monday.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Weekday, &mut Day)> {
+        WEEKDAYS.into_iter().zip([
+            &mut self.monday,
+            &mut self.tuesday,
+            &mut self.wednesday,
+            &mut self.thursday,
+            &mut self.friday,
+            &mut self.saturday,
+            &mut self.sunday,
+        ])
+    }
+
+    /// Iterate over all bells of the week, starting on Monday.
+    pub fn bells(&self) -> impl Iterator<Item = &BellTime> {
+        self.iter().flat_map(|(_, day)| day)
+    }
+
+    /// Get the days up to the last day with bells, starting on Monday, like the days of a week
+    /// variant in `no_std` builds.
+    pub(crate) fn school_days(&self) -> Vec<&Day> {
+        let mut days = self.iter().map(|(_, day)| day).collect::<Vec<_>>();
+        while days.last().is_some_and(|day| day.is_empty()) {
+            days.pop();
+        }
+        days
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for Days {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for Days {}
+
+#[cfg(feature = "std")]
+impl Hash for Days {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (_, day) in self.iter() {
+            day.hash(state);
+        }
+    }
+}
+
+#[cfg(all(feature = "diff", feature = "std"))]
+#[derive(Debug)]
+/// Differences between two [`Days`].
+pub struct DaysDiff {
+    /// Differences in the bells on Monday.
+    pub monday: <Day as Diff>::Repr,
+    /// Differences in the bells on Tuesday.
+    pub tuesday: <Day as Diff>::Repr,
+    /// Differences in the bells on Wednesday.
+    pub wednesday: <Day as Diff>::Repr,
+    /// Differences in the bells on Thursday.
+    pub thursday: <Day as Diff>::Repr,
+    /// Differences in the bells on Friday.
+    pub friday: <Day as Diff>::Repr,
+    /// Differences in the bells on Saturday.
+    pub saturday: <Day as Diff>::Repr,
+    /// Differences in the bells on Sunday.
+    pub sunday: <Day as Diff>::Repr,
+}
+
+#[cfg(all(feature = "diff", feature = "std"))]
+impl Diff for Days {
+    type Repr = DaysDiff;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        Self::Repr {
+            monday: self.monday.diff(&other.monday),
+            tuesday: self.tuesday.diff(&other.tuesday),
+            wednesday: self.wednesday.diff(&other.wednesday),
+            thursday: self.thursday.diff(&other.thursday),
+            friday: self.friday.diff(&other.friday),
+            saturday: self.saturday.diff(&other.saturday),
+            sunday: self.sunday.diff(&other.sunday),
+        }
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        self.monday.apply(&diff.monday);
+        self.tuesday.apply(&diff.tuesday);
+        self.wednesday.apply(&diff.wednesday);
+        self.thursday.apply(&diff.thursday);
+        self.friday.apply(&diff.friday);
+        self.saturday.apply(&diff.saturday);
+        self.sunday.apply(&diff.sunday);
+    }
+
+    fn identity() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Index<Weekday> for Days {
+    type Output = Day;
+
+    fn index(&self, weekday: Weekday) -> &Day {
+        self.get(weekday)
+    }
+}
+
+#[cfg(feature = "std")]
+impl IndexMut<Weekday> for Days {
+    fn index_mut(&mut self, weekday: Weekday) -> &mut Day {
+        self.get_mut(weekday)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Days {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Days".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Bells of each day, starting on Monday. Missing days have no school.",
+            "type": "array",
+            "items": generator.subschema_for::<Day>(),
+            "maxItems": 7,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Serialize for Days {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let days = if !self.sunday.is_empty() {
+            7
+        } else if !self.saturday.is_empty() {
+            6
+        } else {
+            5
+        };
+        serializer.collect_seq(self.iter().take(days).map(|(_, day)| day))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Days {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let days = Vec::<Day>::deserialize(deserializer)?;
+        if days.len() > WEEKDAYS.len() {
+            return Err(D::Error::invalid_length(days.len(), &"at most 7 days"));
+        }
+        let mut result = Self {
+            loaded_length: Some(days.len()),
+            ..Self::default()
+        };
+        for ((_, day), bells) in result.iter_mut().zip(days) {
+            *day = bells;
+        }
+        Ok(result)
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "std")] {
         #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         #[cfg_attr(feature = "diff", derive(Diff))]
         #[cfg_attr(feature = "diff", diff(attr(
             #[derive(Debug)]
@@ -139,6 +416,28 @@ cfg_if! {
             pub tags: Vec<String>,
             /// Version of the school data.
             pub version: String,
+            /// Explicit activations of week variants, which take precedence over the automatic cycle.
+            /// Later activations take precedence over earlier ones.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub variant_activations: Vec<VariantActivation>,
+            /// Anchor of the cycle of week variants. If [`None`], the cycle is based on ISO week
+            /// numbers.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub cycle_anchor: Option<CycleAnchor>,
+            /// Calendar of the school, describing when school is in session.
+            #[serde(default, skip_serializing_if = "Calendar::is_empty")]
+            pub calendar: Calendar,
+            /// Date-specific overrides of the bells, which take precedence over the calendar and the
+            /// week variant cycle.
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub overrides: Vec<DayOverride>,
+            /// IANA timezone of the school, like `Australia/Sydney`. If [`None`], times are assumed
+            /// to already be in the school's local time.
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            pub timezone: Option<String>,
+            /// Unknown fields, kept so that they are saved unchanged.
+            #[serde(flatten)]
+            pub unknown: UnknownFields,
         }
     } else {
         #[derive(Debug, Clone)]
@@ -146,10 +445,47 @@ cfg_if! {
         pub struct School<'a, 'b, 'c> {
             /// Bell times for each week variant.
             pub bell_times: &'a [Week<'b, 'c>],
+            /// Anchor of the cycle of week variants. If [`None`], the cycle is based on ISO week
+            /// numbers.
+            pub cycle_anchor: Option<CycleAnchor>,
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl School {
+    /// Create a school created by the user, with no week variants, at the current version of the
+    /// data format ([`crate::migration::CURRENT_VERSION`]).
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            notices: Vec::new(),
+            links: Vec::new(),
+            user_created: true,
+            bell_times: Vec::new(),
+            latitude: 0.,
+            longitude: 0.,
+            location: String::new(),
+            tags: Vec::new(),
+            version: crate::migration::CURRENT_VERSION.to_string(),
+            variant_activations: Vec::new(),
+            cycle_anchor: None,
+            calendar: Calendar::default(),
+            overrides: Vec::new(),
+            timezone: None,
+            unknown: UnknownFields::new(),
+        }
+    }
+
+    /// Get the timezone of the school.
+    /// Returns [`None`] if the school has no timezone, or if it isn't a valid IANA timezone.
+    #[must_use]
+    pub fn tz(&self) -> Option<Tz> {
+        self.timezone.as_ref()?.parse().ok()
+    }
+}
+
 #[cfg(feature = "std")]
 impl Display for School {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -164,7 +500,7 @@ impl Display for School {
                 if self.bell_times.len() == 1 { "" } else { "s" },
                 self.bell_times
                     .iter()
-                    .map(|Week { days, .. }| days.iter().flatten().count())
+                    .map(|Week { days, .. }| days.bells().count())
                     .sum::<usize>(),
                 self.location
             )
@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+use chrono::NaiveDate;
+#[cfg(feature = "diff")]
+use diff::Diff;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+/// Explicit activation of a week variant of a [`super::School`] for a range of dates.
+/// This is used to reach variants which are not included in the automatic cycle, like exam weeks.
+pub struct VariantActivation {
+    /// Index of the week variant in [`super::School::bell_times`].
+    pub variant: usize,
+    /// First date the variant is active on.
+    pub start: NaiveDate,
+    /// Last date the variant is active on, inclusive.
+    pub end: NaiveDate,
+}
+
+impl VariantActivation {
+    /// Returns `true` if the variant is active on the given date.
+    #[must_use]
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+#[derive(Debug)]
+#[cfg(feature = "diff")]
+#[allow(clippy::module_name_repetitions)]
+/// Differences between two [`VariantActivation`]s.
+pub struct VariantActivationDiff {
+    /// Differences in the variants of the activations.
+    pub variant: Option<usize>,
+    /// Differences in the start dates of the activations.
+    pub start: Option<NaiveDate>,
+    /// Differences in the end dates of the activations.
+    pub end: Option<NaiveDate>,
+}
+
+#[cfg(feature = "diff")]
+impl Diff for VariantActivation {
+    type Repr = VariantActivationDiff;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        Self::Repr {
+            variant: (self.variant != other.variant).then_some(other.variant),
+            start: (self.start != other.start).then_some(other.start),
+            end: (self.end != other.end).then_some(other.end),
+        }
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        if let Some(variant) = diff.variant {
+            self.variant = variant;
+        }
+        if let Some(start) = diff.start {
+            self.start = start;
+        }
+        if let Some(end) = diff.end {
+            self.end = end;
+        }
+    }
+
+    fn identity() -> Self {
+        Self {
+            variant: 0,
+            start: NaiveDate::default(),
+            end: NaiveDate::default(),
+        }
+    }
+}
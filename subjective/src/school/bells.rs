@@ -11,6 +11,8 @@ use core::fmt::{self, Write};
 use diff::{Diff, OptionDiff};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+#[cfg(feature = "std")]
+use serde_json::Value;
 
 use strum_macros::Display;
 #[cfg(feature = "std")]
@@ -21,15 +23,26 @@ use uuid::Uuid;
 #[cfg(feature = "std")]
 use crate::subjects::Subject;
 #[cfg(feature = "std")]
-use crate::{Subjective, color::Color};
+use crate::{Subjective, color::Color, unknown::UnknownFields};
 
 pub(crate) mod ir;
 
+#[cfg(feature = "std")]
+/// Key in [`BellTime::unknown`] of the subject of a bell which isn't a class.
+const SUBJECT_ID: &str = "subjectID";
+#[cfg(feature = "std")]
+/// Key in [`BellTime::unknown`] of the location of a bell which isn't a class.
+const LOCATION: &str = "location";
+#[cfg(feature = "std")]
+/// Key in [`BellTime::unknown`] of the unknown fields of the bell type.
+const BELL_TYPE: &str = "bellType";
+
 #[derive(Debug, Clone)]
 /// Bell-related data.
 pub struct BellTime {
     #[cfg(feature = "std")]
-    /// UUID of the bell.
+    /// UUID of the bell. If the data doesn't have one, it is nil until assigned by
+    /// [`crate::school::School::assign_ids`], which is done when data is loaded or imported.
     pub id: Uuid,
     #[cfg(feature = "std")]
     /// Name of the bell.
@@ -40,6 +53,25 @@ pub struct BellTime {
     pub bell_data: Option<BellData>,
     /// Whether the bell is enabled. Notifications will not be sent for disabled bells.
     pub enabled: bool,
+    #[cfg(feature = "std")]
+    /// SF Symbols icon of the bell type, if it isn't the icon given by [`BellData::icon`].
+    pub icon: Option<String>,
+    #[cfg(feature = "std")]
+    /// Unknown fields, kept so that they are saved unchanged. This includes the `subjectID` and
+    /// `location` of a bell which isn't a class, and the unknown fields of its bell type under
+    /// `bellType`.
+    pub unknown: UnknownFields,
+}
+
+#[cfg(all(feature = "binary", not(feature = "std")))]
+impl BellTime {
+    /// Disabled bell at midnight without a type, which can fill storage before bells are read
+    /// into it by [`crate::binary::from_binary`].
+    pub const EMPTY: Self = Self {
+        time: NaiveTime::MIN,
+        bell_data: None,
+        enabled: false,
+    };
 }
 
 impl core::hash::Hash for BellTime {
@@ -49,6 +81,10 @@ impl core::hash::Hash for BellTime {
         self.time.hash(state);
         self.bell_data.hash(state);
         self.enabled.hash(state);
+        #[cfg(feature = "std")]
+        self.icon.hash(state);
+        #[cfg(feature = "std")]
+        self.unknown.hash(state);
     }
 }
 
@@ -59,6 +95,8 @@ impl PartialEq for BellTime {
             && self.time == other.time
             && self.bell_data == other.bell_data
             && self.enabled == other.enabled
+            && self.icon == other.icon
+            && self.unknown == other.unknown
     }
 }
 
@@ -79,6 +117,10 @@ pub struct BellTimeDiff {
     pub bell_data: OptionDiff<BellData>,
     /// Differences in the enabled status of the bell.
     pub enabled: Option<bool>,
+    /// Differences in the icon of the bell type.
+    pub icon: OptionDiff<String>,
+    /// Differences in the unknown fields of the bell.
+    pub unknown: Option<UnknownFields>,
 }
 
 #[cfg(all(feature = "diff", feature = "std"))]
@@ -96,6 +138,8 @@ impl Diff for BellTime {
             time: other.time - self.time,
             bell_data: self.bell_data.diff(&other.bell_data),
             enabled: self.enabled.diff(&other.enabled),
+            icon: self.icon.diff(&other.icon),
+            unknown: self.unknown.diff(&other.unknown),
         }
     }
 
@@ -107,6 +151,8 @@ impl Diff for BellTime {
         self.time += diff.time;
         self.bell_data.apply(&diff.bell_data);
         self.enabled.apply(&diff.enabled);
+        self.icon.apply(&diff.icon);
+        self.unknown.apply(&diff.unknown);
     }
 
     fn identity() -> Self {
@@ -116,6 +162,8 @@ impl Diff for BellTime {
             time: NaiveTime::default(),
             bell_data: None,
             enabled: false,
+            icon: None,
+            unknown: UnknownFields::new(),
         }
     }
 }
@@ -137,34 +185,82 @@ impl BellTime {
     pub(crate) fn from_ir(bell_time: &ir::BellTime) -> Option<Self> {
         let time = NaiveTime::from_hms_opt(bell_time.hour, bell_time.minute, 0)?;
         let bell_data = BellData::from_ir(bell_time);
+        let icon = bell_time
+            .bell_type
+            .as_ref()
+            .map(|bell_type| bell_type.icon.clone())
+            .filter(|icon| bell_data.as_ref().and_then(BellData::icon).as_ref() != Some(icon));
+        let mut unknown = bell_time.unknown.clone();
+        if !bell_data.as_ref().is_some_and(BellData::is_class) {
+            if let Some(subject_id) = bell_time.subject_id {
+                unknown.0.insert(
+                    SUBJECT_ID.to_string(),
+                    Value::String(subject_id.to_string()),
+                );
+            }
+            if !bell_time.location.is_empty() {
+                unknown.0.insert(
+                    LOCATION.to_string(),
+                    Value::String(bell_time.location.clone()),
+                );
+            }
+        }
+        if let Some(bell_type) = &bell_time.bell_type
+            && !bell_type.unknown.is_empty()
+        {
+            unknown.0.insert(
+                BELL_TYPE.to_string(),
+                Value::Object(bell_type.unknown.0.clone().into_iter().collect()),
+            );
+        }
         Some(Self {
             id: bell_time.id,
             name: bell_time.name.clone(),
             time,
             bell_data,
             enabled: bell_time.enabled,
+            icon,
+            unknown,
         })
     }
 
     pub(crate) fn to_ir(&self) -> ir::BellTime {
+        let mut unknown = self.unknown.clone();
+        let mut take_string = |key| match unknown.0.remove(key) {
+            Some(Value::String(value)) => Some(value),
+            _ => None,
+        };
+        let (subject_id, location) = match self.bell_data.as_ref() {
+            Some(BellData::Class {
+                subject_id,
+                location,
+            }) => {
+                take_string(SUBJECT_ID);
+                take_string(LOCATION);
+                (Some(*subject_id), location.clone())
+            }
+            _ => (
+                take_string(SUBJECT_ID).and_then(|subject_id| Uuid::parse_str(&subject_id).ok()),
+                take_string(LOCATION).unwrap_or_default(),
+            ),
+        };
+        let type_unknown = match unknown.0.remove(BELL_TYPE) {
+            Some(Value::Object(fields)) => UnknownFields(fields.into_iter().collect()),
+            _ => UnknownFields::new(),
+        };
         ir::BellTime {
             id: self.id,
             name: self.name.clone(),
             hour: self.time.hour(),
             minute: self.time.minute(),
-            bell_type: self.bell_data.as_ref().and_then(BellData::to_ir),
-            subject_id: self
+            bell_type: self
                 .bell_data
                 .as_ref()
-                .and_then(|bell_data| match bell_data {
-                    BellData::Class { subject_id, .. } => Some(*subject_id),
-                    _ => None,
-                }),
-            location: match self.bell_data.as_ref() {
-                Some(BellData::Class { location, .. }) => location.clone(),
-                _ => String::new(),
-            },
+                .and_then(|bell_data| bell_data.to_ir(self.icon.as_deref(), type_unknown)),
+            subject_id,
+            location,
             enabled: self.enabled,
+            unknown,
         }
     }
 
@@ -212,7 +308,7 @@ impl BellTime {
     /// # Examples
     ///
     /// ```rust
-    /// # use subjective::{school::{bells::{BellTime, BellData}, School, Week}, subjects::Subject, Subjective};
+    /// # use subjective::{school::{bells::{BellTime, BellData}, Days, School, Week}, subjects::Subject, Subjective};
     /// # use uuid::Uuid;
     /// # use chrono::NaiveTime;
     /// # use std::default::Default;
@@ -228,6 +324,7 @@ impl BellTime {
     /// #             icon: "".to_string(),
     /// #             id: Uuid::nil(),
     /// #             locations: vec!["D14".to_string()],
+    /// #             unknown: Default::default(),
     /// #         }
     /// #     ],
     /// #     school: School {
@@ -236,8 +333,9 @@ impl BellTime {
     /// #             Week {
     /// #                 id: Uuid::new_v4(),
     /// #                 name: "Week 1".to_string(),
-    /// #                 days: vec![
-    /// #                     vec![
+    /// #                 days: {
+    /// #                     let mut days = Days::default();
+    /// #                     days.monday = vec![
     /// #                         BellTime {
     /// #                             id: Uuid::new_v4(),
     /// #                             name: "Period 1".to_string(),
@@ -247,14 +345,14 @@ impl BellTime {
     /// #                                 location: "D14".to_string(),
     /// #                             }),
     /// #                             enabled: true,
+    /// #                             icon: None,
+    /// #                             unknown: Default::default(),
     /// #                         }
-    /// #                     ],
-    /// #                     Vec::new(),
-    /// #                     Vec::new(),
-    /// #                     Vec::new(),
-    /// #                     Vec::new(),
-    /// #                 ],
-    /// #                 cyclical: true
+    /// #                     ];
+    /// #                     days
+    /// #                 },
+    /// #                 cyclical: true,
+    /// #                 unknown: Default::default(),
     /// #             }
     /// #         ],
     /// #         notices: Default::default(),
@@ -265,7 +363,14 @@ impl BellTime {
     /// #         location: Default::default(),
     /// #         tags: Default::default(),
     /// #         version: Default::default(),
+    /// #         variant_activations: Default::default(),
+    /// #         cycle_anchor: Default::default(),
+    /// #         calendar: Default::default(),
+    /// #         overrides: Default::default(),
+    /// #         timezone: Default::default(),
+    /// #         unknown: Default::default(),
     /// #     },
+    /// #     unknown: Default::default(),
     /// # };
     /// let bell_time = BellTime {
     ///     id: Uuid::new_v4(),
@@ -276,6 +381,8 @@ impl BellTime {
     ///         location: "D14".to_string(),
     ///     }),
     ///     enabled: true,
+    ///     icon: None,
+    ///     unknown: Default::default(),
     /// };
     ///
     /// assert_eq!(bell_time.format(&data).unwrap(), "Maths in D14 Period 1".to_string());
@@ -294,7 +401,7 @@ impl BellTime {
     /// # Examples
     ///
     /// ```rust
-    /// # use subjective::{school::{bells::{BellTime, BellData}, School, Week}, subjects::Subject, Subjective};
+    /// # use subjective::{school::{bells::{BellTime, BellData}, Days, School, Week}, subjects::Subject, Subjective};
     /// # use uuid::Uuid;
     /// # use chrono::NaiveTime;
     /// # use std::default::Default;
@@ -310,6 +417,7 @@ impl BellTime {
     /// #             icon: "".to_string(),
     /// #             id: Uuid::nil(),
     /// #             locations: vec!["D14".to_string()],
+    /// #             unknown: Default::default(),
     /// #         }
     /// #     ],
     /// #     school: School {
@@ -318,8 +426,9 @@ impl BellTime {
     /// #             Week {
     /// #                 id: Uuid::new_v4(),
     /// #                 name: "Week 1".to_string(),
-    /// #                 days: vec![
-    /// #                     vec![
+    /// #                 days: {
+    /// #                     let mut days = Days::default();
+    /// #                     days.monday = vec![
     /// #                         BellTime {
     /// #                             id: Uuid::new_v4(),
     /// #                             name: "Period 1".to_string(),
@@ -329,14 +438,14 @@ impl BellTime {
     /// #                                 location: "D14".to_string(),
     /// #                             }),
     /// #                             enabled: true,
+    /// #                             icon: None,
+    /// #                             unknown: Default::default(),
     /// #                         }
-    /// #                     ],
-    /// #                     Vec::new(),
-    /// #                     Vec::new(),
-    /// #                     Vec::new(),
-    /// #                     Vec::new(),
-    /// #                 ],
-    /// #                 cyclical: true
+    /// #                     ];
+    /// #                     days
+    /// #                 },
+    /// #                 cyclical: true,
+    /// #                 unknown: Default::default(),
     /// #             }
     /// #         ],
     /// #         notices: Default::default(),
@@ -347,7 +456,14 @@ impl BellTime {
     /// #         location: Default::default(),
     /// #         tags: Default::default(),
     /// #         version: Default::default(),
+    /// #         variant_activations: Default::default(),
+    /// #         cycle_anchor: Default::default(),
+    /// #         calendar: Default::default(),
+    /// #         overrides: Default::default(),
+    /// #         timezone: Default::default(),
+    /// #         unknown: Default::default(),
     /// #     },
+    /// #     unknown: Default::default(),
     /// # };
     /// let bell_time = BellTime {
     ///     id: Uuid::new_v4(),
@@ -358,6 +474,8 @@ impl BellTime {
     ///         location: "D14".to_string(),
     ///     }),
     ///     enabled: true,
+    ///     icon: None,
+    ///     unknown: Default::default(),
     /// };
     ///
     /// assert_eq!(bell_time.format_with_time(&data).unwrap(), "Maths in D14 Period 1 9:00 AM".to_string());
@@ -387,6 +505,17 @@ impl Serialize for BellTime {
     }
 }
 
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for BellTime {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        ir::BellTime::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        ir::BellTime::json_schema(generator)
+    }
+}
+
 #[derive(Display, Debug, Clone, PartialEq, Eq, Hash)]
 /// Data associated with a [`BellTime`].
 pub enum BellData {
@@ -407,6 +536,14 @@ pub enum BellData {
     Study,
     /// Miscellaneous break.
     Pause,
+    /// Bell of a type which isn't known, like a type added by a newer version of the Subjective
+    /// app. The name of the type is kept, so that it is saved unchanged.
+    #[cfg_attr(feature = "std", strum(to_string = "{name}"))]
+    Other {
+        #[cfg(feature = "std")]
+        /// Name of the type.
+        name: String,
+    },
 }
 
 #[cfg(all(feature = "diff", feature = "std"))]
@@ -428,6 +565,11 @@ pub enum BellDataDiff {
     Study,
     /// The [`BellData`] changed to [`BellData::Pause`].
     Pause,
+    /// The [`BellData`] changed to [`BellData::Other`].
+    Other {
+        /// Name of the type.
+        name: String,
+    },
 }
 
 #[cfg(all(feature = "diff", feature = "std"))]
@@ -457,6 +599,9 @@ impl Diff for BellData {
             | (Self::Break, Self::Break)
             | (Self::Study, Self::Study)
             | (Self::Pause, Self::Pause) => None,
+            (Self::Other { name: name_a }, Self::Other { name: name_b }) if name_a == name_b => {
+                None
+            }
             _ => Some(match other {
                 Self::Class {
                     subject_id,
@@ -469,6 +614,7 @@ impl Diff for BellData {
                 Self::Break => BellDataDiff::Break,
                 Self::Study => BellDataDiff::Study,
                 Self::Pause => BellDataDiff::Pause,
+                Self::Other { name } => BellDataDiff::Other { name: name.clone() },
             }),
         }
     }
@@ -500,6 +646,9 @@ impl Diff for BellData {
             Some(BellDataDiff::Break) => *self = Self::Break,
             Some(BellDataDiff::Study) => *self = Self::Study,
             Some(BellDataDiff::Pause) => *self = Self::Pause,
+            Some(BellDataDiff::Other { name }) => {
+                *self = Self::Other { name: name.clone() };
+            }
             None => {}
         }
     }
@@ -513,7 +662,7 @@ impl BellData {
     #[cfg(feature = "std")]
     #[must_use]
     /// Get the SF Symbols name of the icon associated with the bell type.
-    /// Returns [`None`] when the bell type is [`BellData::Class`].
+    /// Returns [`None`] when the bell type is [`BellData::Class`] or [`BellData::Other`].
     ///
     /// # Examples
     ///
@@ -530,7 +679,7 @@ impl BellData {
     /// ```
     pub fn icon(&self) -> Option<String> {
         match self {
-            Self::Class { .. } => None,
+            Self::Class { .. } | Self::Other { .. } => None,
             Self::Time => Some("clock.fill".to_string()),
             Self::Break => Some("fork.knife".to_string()),
             Self::Study => Some("book.fill".to_string()),
@@ -561,18 +710,21 @@ impl BellData {
                 "Break" => Some(Self::Break),
                 "Study" => Some(Self::Study),
                 "Pause" => Some(Self::Pause),
-                _ => None,
+                name => Some(Self::Other {
+                    name: name.to_string(),
+                }),
             },
         )
     }
 
     #[cfg(feature = "std")]
-    pub(crate) fn to_ir(&self) -> Option<ir::BellType> {
+    pub(crate) fn to_ir(&self, icon: Option<&str>, unknown: UnknownFields) -> Option<ir::BellType> {
         match self {
             Self::Class { .. } => None,
             _ => Some(ir::BellType {
                 name: self.to_string(),
-                icon: self.icon().unwrap_or_default(),
+                icon: icon.map_or_else(|| self.icon().unwrap_or_default(), ToString::to_string),
+                unknown,
             }),
         }
     }
@@ -606,6 +758,12 @@ impl BellData {
     pub const fn is_pause(&self) -> bool {
         matches!(self, Self::Pause)
     }
+
+    /// Returns `true` if the bell data is [`BellData::Other`].
+    #[must_use]
+    pub const fn is_other(&self) -> bool {
+        matches!(self, Self::Other { .. })
+    }
 }
 
 #[cfg(all(test, feature = "std"))]
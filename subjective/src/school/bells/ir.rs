@@ -3,29 +3,45 @@
 use serde::{Deserialize, Deserializer, Serialize, de::Error};
 use uuid::Uuid;
 
+use crate::unknown::UnknownFields;
+
 const fn default_enabled() -> bool {
     true
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
+/// Bell as it is stored in Subjective data files.
 pub struct BellTime {
-    #[serde(default = "Uuid::new_v4")]
+    /// UUID of the bell.
+    #[serde(default)]
     pub id: Uuid,
+    /// Name of the bell.
     pub name: String,
+    /// Minute of the time of the bell.
     pub minute: u32,
+    /// Hour of the time of the bell, from 0 to 23.
     pub hour: u32,
+    /// UUID of the subject of a class, or an empty string if the bell isn't a class.
     #[serde(
         rename = "subjectID",
         deserialize_with = "deserialise_subject_id",
         default
     )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
     pub subject_id: Option<Uuid>,
+    /// Location of a class.
     #[serde(default)]
     pub location: String,
+    /// Type of a bell which isn't a class.
     pub bell_type: Option<BellType>,
+    /// Whether the bell is enabled.
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Unknown fields, kept so that they are saved unchanged.
+    #[serde(flatten)]
+    pub unknown: UnknownFields,
 }
 
 fn deserialise_subject_id<'de, D>(deserializer: D) -> Result<Option<Uuid>, D::Error>
@@ -41,8 +57,16 @@ where
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+/// Type of a bell which isn't a class.
 pub struct BellType {
+    /// Name of the type, which is `Time`, `Break`, `Study`, `Pause`, or a type which
+    /// isn't known.
     pub name: String,
+    /// SF Symbols icon of the type.
     #[serde(rename = "iconName")]
     pub icon: String,
+    /// Unknown fields, kept so that they are saved unchanged.
+    #[serde(flatten)]
+    pub unknown: UnknownFields,
 }
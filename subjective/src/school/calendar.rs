@@ -0,0 +1,262 @@
+#![cfg(feature = "std")]
+
+use core::fmt::{self, Display, Formatter};
+
+use chrono::NaiveDate;
+#[cfg(feature = "diff")]
+use diff::Diff;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "diff", derive(Diff))]
+#[cfg_attr(feature = "diff", diff(attr(
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+)))]
+#[serde(rename_all = "camelCase")]
+/// Calendar of a [`super::School`], describing when school is in session.
+pub struct Calendar {
+    /// Terms of the school year. If there are no terms, every date is considered to be in term.
+    #[serde(default)]
+    pub terms: Vec<Term>,
+    /// Holidays, which may fall inside terms, like public holidays.
+    #[serde(default)]
+    pub holidays: Vec<Holiday>,
+    /// One-off days without classes, like staff development days.
+    #[serde(default)]
+    pub pupil_free_days: Vec<PupilFreeDay>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+/// A term of the school year.
+pub struct Term {
+    /// Name of the term.
+    pub name: String,
+    /// First date of the term.
+    pub start: NaiveDate,
+    /// Last date of the term, inclusive.
+    pub end: NaiveDate,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+/// A range of dates without school.
+pub struct Holiday {
+    /// Name of the holiday.
+    pub name: String,
+    /// First date of the holiday.
+    pub start: NaiveDate,
+    /// Last date of the holiday, inclusive.
+    pub end: NaiveDate,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+/// A single day without classes for students.
+pub struct PupilFreeDay {
+    /// Name of the pupil-free day.
+    pub name: String,
+    /// Date of the pupil-free day.
+    pub date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Reason that there is no school on a date, as given by [`Calendar::no_school_reason`].
+pub enum NoSchoolReason<'a> {
+    /// The date is outside of every term.
+    OutOfTerm,
+    /// The date is during a holiday.
+    Holiday(&'a Holiday),
+    /// The date is a pupil-free day.
+    PupilFreeDay(&'a PupilFreeDay),
+}
+
+impl Display for NoSchoolReason<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfTerm => write!(f, "Out of term"),
+            Self::Holiday(Holiday { name, .. }) => write!(f, "{name}"),
+            Self::PupilFreeDay(PupilFreeDay { name, .. }) => write!(f, "{name} (pupil-free day)"),
+        }
+    }
+}
+
+impl Term {
+    /// Returns `true` if the given date is in the term.
+    #[must_use]
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+impl Holiday {
+    /// Returns `true` if the given date is in the holiday.
+    #[must_use]
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+impl Calendar {
+    /// Returns `true` if the calendar has no terms, holidays, or pupil-free days.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.holidays.is_empty() && self.pupil_free_days.is_empty()
+    }
+
+    /// Get the term which the given date is in.
+    #[must_use]
+    pub fn term(&self, date: NaiveDate) -> Option<&Term> {
+        self.terms.iter().find(|term| term.contains(date))
+    }
+
+    /// Get the reason that there is no school on the given date.
+    /// Returns [`None`] if school is in session, which does not mean that there are bells on the
+    /// date, since weekends are not considered.
+    #[must_use]
+    pub fn no_school_reason(&self, date: NaiveDate) -> Option<NoSchoolReason<'_>> {
+        if let Some(pupil_free_day) = self.pupil_free_days.iter().find(|day| day.date == date) {
+            return Some(NoSchoolReason::PupilFreeDay(pupil_free_day));
+        }
+        if let Some(holiday) = self.holidays.iter().find(|holiday| holiday.contains(date)) {
+            return Some(NoSchoolReason::Holiday(holiday));
+        }
+        if !self.terms.is_empty() && self.term(date).is_none() {
+            return Some(NoSchoolReason::OutOfTerm);
+        }
+        None
+    }
+
+    /// Returns `true` if school is in session on the given date.
+    #[must_use]
+    pub fn is_school_day(&self, date: NaiveDate) -> bool {
+        self.no_school_reason(date).is_none()
+    }
+}
+
+#[derive(Debug)]
+#[cfg(feature = "diff")]
+#[allow(clippy::module_name_repetitions)]
+/// Differences between two [`Term`]s.
+pub struct TermDiff {
+    /// Differences in the names of the terms.
+    pub name: Option<String>,
+    /// Differences in the start dates of the terms.
+    pub start: Option<NaiveDate>,
+    /// Differences in the end dates of the terms.
+    pub end: Option<NaiveDate>,
+}
+
+#[cfg(feature = "diff")]
+impl Diff for Term {
+    type Repr = TermDiff;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        Self::Repr {
+            name: self.name.diff(&other.name),
+            start: (self.start != other.start).then_some(other.start),
+            end: (self.end != other.end).then_some(other.end),
+        }
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        self.name.apply(&diff.name);
+        if let Some(start) = diff.start {
+            self.start = start;
+        }
+        if let Some(end) = diff.end {
+            self.end = end;
+        }
+    }
+
+    fn identity() -> Self {
+        Self {
+            name: String::new(),
+            start: NaiveDate::default(),
+            end: NaiveDate::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg(feature = "diff")]
+#[allow(clippy::module_name_repetitions)]
+/// Differences between two [`Holiday`]s.
+pub struct HolidayDiff {
+    /// Differences in the names of the holidays.
+    pub name: Option<String>,
+    /// Differences in the start dates of the holidays.
+    pub start: Option<NaiveDate>,
+    /// Differences in the end dates of the holidays.
+    pub end: Option<NaiveDate>,
+}
+
+#[cfg(feature = "diff")]
+impl Diff for Holiday {
+    type Repr = HolidayDiff;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        Self::Repr {
+            name: self.name.diff(&other.name),
+            start: (self.start != other.start).then_some(other.start),
+            end: (self.end != other.end).then_some(other.end),
+        }
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        self.name.apply(&diff.name);
+        if let Some(start) = diff.start {
+            self.start = start;
+        }
+        if let Some(end) = diff.end {
+            self.end = end;
+        }
+    }
+
+    fn identity() -> Self {
+        Self {
+            name: String::new(),
+            start: NaiveDate::default(),
+            end: NaiveDate::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg(feature = "diff")]
+#[allow(clippy::module_name_repetitions)]
+/// Differences between two [`PupilFreeDay`]s.
+pub struct PupilFreeDayDiff {
+    /// Differences in the names of the pupil-free days.
+    pub name: Option<String>,
+    /// Differences in the dates of the pupil-free days.
+    pub date: Option<NaiveDate>,
+}
+
+#[cfg(feature = "diff")]
+impl Diff for PupilFreeDay {
+    type Repr = PupilFreeDayDiff;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        Self::Repr {
+            name: self.name.diff(&other.name),
+            date: (self.date != other.date).then_some(other.date),
+        }
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        self.name.apply(&diff.name);
+        if let Some(date) = diff.date {
+            self.date = date;
+        }
+    }
+
+    fn identity() -> Self {
+        Self {
+            name: String::new(),
+            date: NaiveDate::default(),
+        }
+    }
+}
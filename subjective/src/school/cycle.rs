@@ -0,0 +1,71 @@
+use chrono::{Datelike, NaiveDate};
+#[cfg(all(feature = "diff", feature = "std"))]
+use diff::Diff;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+/// A fixed point in the cycle of week variants, like "the week of 2026-01-26 is Week A".
+///
+/// Variants are counted in whole weeks (starting on Monday) from the anchor, so the cycle stays
+/// aligned across years, unlike cycles based on ISO week numbers.
+pub struct CycleAnchor {
+    /// Any date in the anchored week.
+    pub date: NaiveDate,
+    /// Index of the week variant in [`super::School::bell_times`] which is active in the anchored
+    /// week. This should be a cyclical variant.
+    pub variant: usize,
+}
+
+impl CycleAnchor {
+    /// Number of whole weeks from the anchored week to the week of the given date, which is
+    /// negative if the date is before the anchored week.
+    #[must_use]
+    pub fn weeks_until(&self, date: NaiveDate) -> i64 {
+        let monday = |date: NaiveDate| {
+            i64::from(date.num_days_from_ce()) - i64::from(date.weekday().num_days_from_monday())
+        };
+        (monday(date) - monday(self.date)).div_euclid(7)
+    }
+}
+
+#[derive(Debug)]
+#[cfg(all(feature = "diff", feature = "std"))]
+#[allow(clippy::module_name_repetitions)]
+/// Differences between two [`CycleAnchor`]s.
+pub struct CycleAnchorDiff {
+    /// Differences in the dates of the anchors.
+    pub date: Option<NaiveDate>,
+    /// Differences in the variants of the anchors.
+    pub variant: Option<usize>,
+}
+
+#[cfg(all(feature = "diff", feature = "std"))]
+impl Diff for CycleAnchor {
+    type Repr = CycleAnchorDiff;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        Self::Repr {
+            date: (self.date != other.date).then_some(other.date),
+            variant: (self.variant != other.variant).then_some(other.variant),
+        }
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        if let Some(date) = diff.date {
+            self.date = date;
+        }
+        if let Some(variant) = diff.variant {
+            self.variant = variant;
+        }
+    }
+
+    fn identity() -> Self {
+        Self {
+            date: NaiveDate::default(),
+            variant: 0,
+        }
+    }
+}
@@ -5,11 +5,15 @@ use diff::Diff;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::unknown::UnknownFields;
+
 /// Link to websites related to a [`super::School`].
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Link {
-    #[serde(default = "Uuid::new_v4")]
-    /// UUID of the link.
+    #[serde(default)]
+    /// UUID of the link. If the data doesn't have one, it is nil until assigned by
+    /// [`super::School::assign_ids`], which is done when data is loaded or imported.
     pub id: Uuid,
     #[serde(rename = "title")]
     /// Name of the link.
@@ -18,6 +22,9 @@ pub struct Link {
     pub icon: String,
     /// URL that the link points to.
     pub destination: String,
+    /// Unknown fields, kept so that they are saved unchanged.
+    #[serde(flatten)]
+    pub unknown: UnknownFields,
 }
 
 #[derive(Debug)]
@@ -33,6 +40,8 @@ pub struct LinkDiff {
     pub icon: Option<String>,
     /// Differences in the destinations of the links.
     pub destination: Option<String>,
+    /// Differences in the unknown fields of the links.
+    pub unknown: Option<UnknownFields>,
 }
 
 #[cfg(feature = "diff")]
@@ -49,6 +58,7 @@ impl Diff for Link {
             name: self.name.diff(&other.name),
             icon: self.icon.diff(&other.icon),
             destination: self.destination.diff(&other.destination),
+            unknown: self.unknown.diff(&other.unknown),
         }
     }
 
@@ -59,6 +69,7 @@ impl Diff for Link {
         self.name.apply(&diff.name);
         self.icon.apply(&diff.icon);
         self.destination.apply(&diff.destination);
+        self.unknown.apply(&diff.unknown);
     }
 
     fn identity() -> Self {
@@ -67,6 +78,7 @@ impl Diff for Link {
             name: String::new(),
             icon: String::new(),
             destination: String::new(),
+            unknown: UnknownFields::new(),
         }
     }
 }
@@ -5,7 +5,10 @@ use diff::Diff;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::unknown::UnknownFields;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 /// Notices related to a [`super::School`].
 pub struct Notice {
     /// UUID of the notice.
@@ -16,6 +19,9 @@ pub struct Notice {
     pub content: String,
     /// Whether the notice is a priority. If true, displayed more prominently.
     pub priority: bool,
+    /// Unknown fields, kept so that they are saved unchanged.
+    #[serde(flatten)]
+    pub unknown: UnknownFields,
 }
 
 #[derive(Debug)]
@@ -31,6 +37,8 @@ pub struct NoticeDiff {
     pub content: Option<String>,
     /// Differences in the priorities of the notices.
     pub priority: Option<bool>,
+    /// Differences in the unknown fields of the notices.
+    pub unknown: Option<UnknownFields>,
 }
 
 #[cfg(feature = "diff")]
@@ -47,6 +55,7 @@ impl Diff for Notice {
             title: self.title.diff(&other.title),
             content: self.content.diff(&other.content),
             priority: self.priority.diff(&other.priority),
+            unknown: self.unknown.diff(&other.unknown),
         }
     }
 
@@ -57,6 +66,7 @@ impl Diff for Notice {
         self.title.apply(&diff.title);
         self.content.apply(&diff.content);
         self.priority.apply(&diff.priority);
+        self.unknown.apply(&diff.unknown);
     }
 
     fn identity() -> Self {
@@ -65,6 +75,7 @@ impl Diff for Notice {
             title: String::new(),
             content: String::new(),
             priority: false,
+            unknown: UnknownFields::new(),
         }
     }
 }
@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+use chrono::{NaiveDate, Weekday};
+#[cfg(feature = "diff")]
+use diff::Diff;
+use serde::{Deserialize, Serialize};
+
+use crate::school::Day;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+/// Override of the bells of a [`super::School`] on a specific date, like "Friday follows Monday's
+/// timetable" or a shortened assembly day.
+pub struct DayOverride {
+    /// Date which the override applies to.
+    pub date: NaiveDate,
+    /// Description of the override.
+    #[serde(default)]
+    pub name: String,
+    /// Bells which replace the bells of the date.
+    pub replacement: Replacement,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+/// Bells which replace the bells of a date in a [`DayOverride`].
+pub enum Replacement {
+    /// Follow the bells of another weekday, optionally in another week variant.
+    Weekday {
+        /// Index of the week variant in [`super::School::bell_times`] to follow. If [`None`], the
+        /// week variant active on the date is used.
+        #[serde(default)]
+        variant: Option<usize>,
+        /// Weekday to follow.
+        weekday: Weekday,
+    },
+    /// Follow an explicit list of bells.
+    Bells {
+        /// Bells of the day, in ascending order.
+        bells: Day,
+    },
+}
+
+#[derive(Debug)]
+#[cfg(feature = "diff")]
+#[allow(clippy::module_name_repetitions)]
+/// Differences between two [`DayOverride`]s.
+pub struct DayOverrideDiff {
+    /// Differences in the dates of the overrides.
+    pub date: Option<NaiveDate>,
+    /// Differences in the names of the overrides.
+    pub name: Option<String>,
+    /// Differences in the replacements of the overrides.
+    pub replacement: Option<Replacement>,
+}
+
+#[cfg(feature = "diff")]
+impl Diff for DayOverride {
+    type Repr = DayOverrideDiff;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        Self::Repr {
+            date: (self.date != other.date).then_some(other.date),
+            name: self.name.diff(&other.name),
+            replacement: (self.replacement != other.replacement).then(|| other.replacement.clone()),
+        }
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        if let Some(date) = diff.date {
+            self.date = date;
+        }
+        self.name.apply(&diff.name);
+        if let Some(replacement) = &diff.replacement {
+            self.replacement = replacement.clone();
+        }
+    }
+
+    fn identity() -> Self {
+        Self {
+            date: NaiveDate::default(),
+            name: String::new(),
+            replacement: Replacement::Bells { bells: Vec::new() },
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{NaiveTime, TimeDelta};
+
+use crate::school::bells::BellTime;
+
+#[derive(Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+/// A period of the day, spanning from one enabled [`BellTime`] to the next.
+pub struct Period<'a> {
+    /// Start of the period, which is the time of [`Period::bell`].
+    pub start: NaiveTime,
+    /// End of the period, which is the time of the next enabled bell.
+    pub end: NaiveTime,
+    /// Bell which starts the period.
+    pub bell: &'a BellTime,
+}
+
+impl<'a> Period<'a> {
+    /// Iterate over the periods of a day, in ascending order.
+    /// Disabled bells are ignored, and the last enabled bell of the day does not start a period,
+    /// since it has no end.
+    /// Bells must be sorted in ascending order for this function to work correctly.
+    pub fn from_day(day: &'a [BellTime]) -> impl Iterator<Item = Self> + 'a {
+        let enabled = || day.iter().filter(|bell| bell.enabled);
+        enabled().zip(enabled().skip(1)).map(|(bell, next)| Self {
+            start: bell.time,
+            end: next.time,
+            bell,
+        })
+    }
+
+    /// Returns `true` if the period is in progress at the given time.
+    /// Periods include their start, but not their end.
+    #[must_use]
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        self.start <= time && time < self.end
+    }
+
+    /// Length of the period.
+    #[must_use]
+    pub fn duration(&self) -> TimeDelta {
+        self.end - self.start
+    }
+
+    /// Time elapsed since the start of the period, at the given time.
+    /// The result is clamped between zero and [`Period::duration`].
+    #[must_use]
+    pub fn elapsed(&self, time: NaiveTime) -> TimeDelta {
+        time.clamp(self.start, self.end) - self.start
+    }
+
+    /// Time remaining until the end of the period, at the given time.
+    /// The result is clamped between zero and [`Period::duration`].
+    #[must_use]
+    pub fn remaining(&self, time: NaiveTime) -> TimeDelta {
+        self.end - time.clamp(self.start, self.end)
+    }
+
+    /// Fraction of the period which has elapsed at the given time, in the range `0_f64..=1_f64`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn progress(&self, time: NaiveTime) -> f64 {
+        let duration = self.duration().num_seconds();
+        if duration == 0 {
+            return 1.;
+        }
+        self.elapsed(time).num_seconds() as f64 / duration as f64
+    }
+}
@@ -0,0 +1,525 @@
+#![cfg(feature = "std")]
+
+use core::ops::Range;
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, de::Error as _};
+use thiserror::Error;
+use toml::{Spanned, Value, value::Datetime};
+use uuid::Uuid;
+
+use crate::{
+    Subjective,
+    color::Color,
+    normalization::stable_id,
+    school::{
+        Day, Days, School, Week,
+        bells::{BellData, BellTime},
+        calendar::{Calendar, Holiday, PupilFreeDay, Term},
+        cycle::CycleAnchor,
+        link::Link,
+    },
+    subjects::Subject,
+    unknown::UnknownFields,
+};
+
+/// Errors that can occur when compiling a timetable source with [`compile`].
+#[derive(Error, Debug)]
+pub enum CompileError {
+    /// The source isn't valid TOML, or doesn't match the source format.
+    #[error(transparent)]
+    ParseError(#[from] toml::de::Error),
+    /// Two subjects have the same name, so classes can't refer to them.
+    #[error("The subject \"{0}\" on line {1} is already defined.")]
+    DuplicateSubject(String, usize),
+    /// A class refers to a subject which isn't defined.
+    #[error("No subject named \"{0}\" is defined, but it is used on line {1}.")]
+    SubjectNotFound(String, usize),
+    /// A bell with the type `class` doesn't refer to a subject.
+    #[error("The class on line {0} needs a subject.")]
+    MissingSubject(usize),
+    /// A bell which isn't a class refers to a subject.
+    #[error("The bell on line {0} has a subject, but only classes can have subjects.")]
+    UnexpectedSubject(usize),
+    /// The cycle anchor refers to a week variant which isn't defined.
+    #[error("No week variant named \"{0}\" is defined, but it is used on line {1}.")]
+    WeekNotFound(String, usize),
+    /// The timezone of the school isn't a known IANA timezone.
+    #[error("\"{0}\" on line {1} isn't a known timezone.")]
+    InvalidTimezone(String, usize),
+}
+
+/// Time written like `"08:40"`.
+struct SourceTime(NaiveTime);
+
+impl<'de> Deserialize<'de> for SourceTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let time = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&time, "%H:%M")
+            .or_else(|_| NaiveTime::parse_from_str(&time, "%H:%M:%S"))
+            .map(Self)
+            .map_err(|_| {
+                D::Error::custom(format!("expected a time like \"08:40\", found \"{time}\""))
+            })
+    }
+}
+
+/// Date written like `2026-01-26` or `"2026-01-26"`.
+struct SourceDate(NaiveDate);
+
+impl<'de> Deserialize<'de> for SourceDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        match &value {
+            Value::Datetime(Datetime {
+                date: Some(date),
+                time: None,
+                ..
+            }) => NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into()),
+            Value::String(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d").ok(),
+            _ => None,
+        }
+        .map(Self)
+        .ok_or_else(|| D::Error::custom(format!("expected a date like 2026-01-26, found {value}")))
+    }
+}
+
+/// Color written like `"#4a7e80"`.
+struct SourceColor(Color);
+
+impl<'de> Deserialize<'de> for SourceColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let color = String::deserialize(deserializer)?;
+        let component = |index: usize| {
+            color
+                .strip_prefix('#')
+                .filter(|hex| hex.len() == 6)
+                .and_then(|hex| hex.get(index * 2..index * 2 + 2))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .map(|component| f32::from(component) / 255.)
+                .ok_or_else(|| {
+                    D::Error::custom(format!(
+                        "expected a color like \"#4a7e80\", found \"{color}\""
+                    ))
+                })
+        };
+        Ok(Self(Color {
+            red: component(0)?,
+            green: component(1)?,
+            blue: component(2)?,
+        }))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum BellKind {
+    Class,
+    Time,
+    Break,
+    Study,
+    Pause,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BellSource {
+    time: SourceTime,
+    name: String,
+    #[serde(rename = "type")]
+    kind: Option<BellKind>,
+    subject: Option<Spanned<String>>,
+    location: Option<String>,
+    #[serde(default = "enabled")]
+    enabled: bool,
+}
+
+const fn enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WeekSource {
+    #[serde(default = "cyclical")]
+    cyclical: bool,
+    #[serde(default, alias = "mon")]
+    monday: Vec<Spanned<BellSource>>,
+    #[serde(default, alias = "tue")]
+    tuesday: Vec<Spanned<BellSource>>,
+    #[serde(default, alias = "wed")]
+    wednesday: Vec<Spanned<BellSource>>,
+    #[serde(default, alias = "thu")]
+    thursday: Vec<Spanned<BellSource>>,
+    #[serde(default, alias = "fri")]
+    friday: Vec<Spanned<BellSource>>,
+    #[serde(default, alias = "sat")]
+    saturday: Vec<Spanned<BellSource>>,
+    #[serde(default, alias = "sun")]
+    sunday: Vec<Spanned<BellSource>>,
+}
+
+const fn cyclical() -> bool {
+    true
+}
+
+impl WeekSource {
+    fn into_days(self) -> [(Weekday, Vec<Spanned<BellSource>>); 7] {
+        [
+            (Weekday::Mon, self.monday),
+            (Weekday::Tue, self.tuesday),
+            (Weekday::Wed, self.wednesday),
+            (Weekday::Thu, self.thursday),
+            (Weekday::Fri, self.friday),
+            (Weekday::Sat, self.saturday),
+            (Weekday::Sun, self.sunday),
+        ]
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SubjectSource {
+    name: Spanned<String>,
+    id: Option<Uuid>,
+    color: Option<SourceColor>,
+    #[serde(default = "subject_icon")]
+    icon: String,
+    #[serde(default)]
+    locations: Vec<String>,
+}
+
+fn subject_icon() -> String {
+    "book.fill".to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LinkSource {
+    name: String,
+    destination: String,
+    #[serde(default = "link_icon")]
+    icon: String,
+}
+
+fn link_icon() -> String {
+    "link".to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DateRangeSource {
+    name: String,
+    start: SourceDate,
+    end: SourceDate,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DateSource {
+    name: String,
+    date: SourceDate,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct CalendarSource {
+    #[serde(default)]
+    terms: Vec<DateRangeSource>,
+    #[serde(default)]
+    holidays: Vec<DateRangeSource>,
+    #[serde(default)]
+    pupil_free_days: Vec<DateSource>,
+}
+
+impl CalendarSource {
+    fn into_calendar(self) -> Calendar {
+        Calendar {
+            terms: self
+                .terms
+                .into_iter()
+                .map(|term| Term {
+                    name: term.name,
+                    start: term.start.0,
+                    end: term.end.0,
+                })
+                .collect(),
+            holidays: self
+                .holidays
+                .into_iter()
+                .map(|holiday| Holiday {
+                    name: holiday.name,
+                    start: holiday.start.0,
+                    end: holiday.end.0,
+                })
+                .collect(),
+            pupil_free_days: self
+                .pupil_free_days
+                .into_iter()
+                .map(|day| PupilFreeDay {
+                    name: day.name,
+                    date: day.date.0,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CycleAnchorSource {
+    date: SourceDate,
+    week: Spanned<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchoolSource {
+    name: String,
+    #[serde(default)]
+    location: String,
+    #[serde(default)]
+    latitude: f64,
+    #[serde(default)]
+    longitude: f64,
+    #[serde(default)]
+    tags: Vec<String>,
+    timezone: Option<Spanned<String>>,
+    cycle_anchor: Option<CycleAnchorSource>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Source {
+    school: SchoolSource,
+    #[serde(default)]
+    subjects: Vec<SubjectSource>,
+    #[serde(default)]
+    weeks: IndexMap<String, WeekSource>,
+    #[serde(default)]
+    links: Vec<LinkSource>,
+    #[serde(default)]
+    calendar: CalendarSource,
+}
+
+/// Subjects by name, with their UUID and first location.
+type SubjectIds = HashMap<String, (Uuid, Option<String>)>;
+
+/// Get the line number of the start of a span.
+fn line(source: &str, span: &Range<usize>) -> usize {
+    source
+        .get(..span.start)
+        .map_or(0, |before| before.matches('\n').count())
+        + 1
+}
+
+/// Get the line number of the start of a spanned value.
+fn line_of<T>(source: &str, spanned: &Spanned<T>) -> usize {
+    line(source, &spanned.span())
+}
+
+fn create_subjects(
+    source: &str,
+    subjects: Vec<SubjectSource>,
+) -> Result<(Vec<Subject>, SubjectIds), CompileError> {
+    let mut ids = SubjectIds::new();
+    let subjects = subjects
+        .into_iter()
+        .enumerate()
+        .map(|(index, subject)| {
+            let span = subject.name.span();
+            let name = subject.name.into_inner();
+            let id = subject
+                .id
+                .unwrap_or_else(|| stable_id(&format!("subject/{name}")));
+            if ids
+                .insert(name.clone(), (id, subject.locations.first().cloned()))
+                .is_some()
+            {
+                return Err(CompileError::DuplicateSubject(name, line(source, &span)));
+            }
+            Ok(Subject {
+                id,
+                name,
+                color: subject
+                    .color
+                    .map_or_else(|| Color::generated(index), |color| color.0),
+                locations: subject.locations,
+                icon: subject.icon,
+                unknown: UnknownFields::new(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((subjects, ids))
+}
+
+fn create_day(
+    source: &str,
+    bells: Vec<Spanned<BellSource>>,
+    subjects: &SubjectIds,
+) -> Result<Day, CompileError> {
+    bells
+        .into_iter()
+        .map(|bell| {
+            let bell_line = line_of(source, &bell);
+            let bell = bell.into_inner();
+            let bell_data = match (bell.kind, bell.subject) {
+                (Some(BellKind::Class) | None, Some(subject)) => {
+                    let (subject_id, first_location) =
+                        subjects.get(subject.get_ref()).ok_or_else(|| {
+                            CompileError::SubjectNotFound(
+                                subject.get_ref().clone(),
+                                line_of(source, &subject),
+                            )
+                        })?;
+                    Some(BellData::Class {
+                        subject_id: *subject_id,
+                        location: bell
+                            .location
+                            .or_else(|| first_location.clone())
+                            .unwrap_or_default(),
+                    })
+                }
+                (Some(BellKind::Class), None) => {
+                    return Err(CompileError::MissingSubject(bell_line));
+                }
+                (Some(_), Some(_)) => return Err(CompileError::UnexpectedSubject(bell_line)),
+                (Some(BellKind::Time), None) => Some(BellData::Time),
+                (Some(BellKind::Break), None) => Some(BellData::Break),
+                (Some(BellKind::Study), None) => Some(BellData::Study),
+                (Some(BellKind::Pause), None) => Some(BellData::Pause),
+                (None, None) => None,
+            };
+            Ok(BellTime {
+                id: Uuid::nil(),
+                name: bell.name,
+                time: bell.time.0,
+                bell_data,
+                enabled: bell.enabled,
+                icon: None,
+                unknown: UnknownFields::new(),
+            })
+        })
+        .collect()
+}
+
+/// Compile a timetable written in the TOML source format into Subjective data.
+///
+/// The source format is meant to be written by hand: times are written like `"08:40"`, classes
+/// refer to subjects by name, and week variants are named tables, in the order of the cycle.
+///
+/// ```toml
+/// [school]
+/// name = "Example High School"
+/// timezone = "Australia/Sydney"
+///
+/// [[subjects]]
+/// name = "English"
+/// color = "#4a7e80"
+/// locations = ["C17"]
+///
+/// [weeks."Week A"]
+/// monday = [
+///     { time = "08:40", name = "Period 1", subject = "English" },
+///     { time = "09:40", name = "Recess", type = "break" },
+///     { time = "15:00", name = "End", type = "time" },
+/// ]
+/// ```
+///
+/// The `school` table can also have a `location`, `latitude`, `longitude`, `tags`, and a
+/// `cycle_anchor` like `{ date = "2026-01-26", week = "Week A" }`. Subjects can have an `id`, and
+/// an `icon` which defaults to `book.fill`; subjects without a `color` are given one. Week
+/// variants are `cyclical` unless it is `false`, and have a list of bells for each weekday from
+/// `monday` to `sunday`. Bells with a `subject` are classes, which are in the subject's first
+/// location unless they have a `location`. Other bells can have a `type` of `time`, `break`,
+/// `study`, or `pause`, and bells can be disabled with `enabled = false`. There can also be
+/// `[[links]]` with a `name`, `destination`, and `icon`, and a `calendar` table with `terms`,
+/// `holidays`, and `pupil_free_days`.
+///
+/// Subjects without an `id` are given a UUID derived from their name, and the data is normalized
+/// with [`Subjective::normalize`], so compiling the same source always gives the same data.
+///
+/// # Errors
+///
+/// This function will return an error if the source isn't valid TOML or doesn't match the source
+/// format ([`CompileError::ParseError`]), or if it refers to subjects, week variants, or timezones
+/// which don't exist. Errors include the line where the problem is.
+pub fn compile(source: &str) -> Result<Subjective, CompileError> {
+    let file: Source = toml::from_str(source)?;
+    if let Some(timezone) = &file.school.timezone
+        && timezone.get_ref().parse::<Tz>().is_err()
+    {
+        return Err(CompileError::InvalidTimezone(
+            timezone.get_ref().clone(),
+            line_of(source, timezone),
+        ));
+    }
+    let (subjects, subject_ids) = create_subjects(source, file.subjects)?;
+    let bell_times = file
+        .weeks
+        .into_iter()
+        .map(|(name, week)| {
+            let cyclical = week.cyclical;
+            let mut days = Days::default();
+            for (weekday, bells) in week.into_days() {
+                days[weekday] = create_day(source, bells, &subject_ids)?;
+            }
+            Ok(Week {
+                id: Uuid::nil(),
+                name,
+                days,
+                cyclical,
+                unknown: UnknownFields::new(),
+            })
+        })
+        .collect::<Result<Vec<_>, CompileError>>()?;
+    let cycle_anchor = file
+        .school
+        .cycle_anchor
+        .map(|anchor| {
+            bell_times
+                .iter()
+                .position(|week| week.name == *anchor.week.get_ref())
+                .map(|variant| CycleAnchor {
+                    date: anchor.date.0,
+                    variant,
+                })
+                .ok_or_else(|| {
+                    CompileError::WeekNotFound(
+                        anchor.week.get_ref().clone(),
+                        line_of(source, &anchor.week),
+                    )
+                })
+        })
+        .transpose()?;
+    let mut data = Subjective::new(
+        School {
+            links: file
+                .links
+                .into_iter()
+                .map(|link| Link {
+                    id: Uuid::nil(),
+                    name: link.name,
+                    icon: link.icon,
+                    destination: link.destination,
+                    unknown: UnknownFields::new(),
+                })
+                .collect(),
+            bell_times,
+            latitude: file.school.latitude,
+            longitude: file.school.longitude,
+            location: file.school.location,
+            tags: file.school.tags,
+            cycle_anchor,
+            calendar: file.calendar.into_calendar(),
+            timezone: file.school.timezone.map(Spanned::into_inner),
+            ..School::new(file.school.name)
+        },
+        subjects,
+    );
+    data.normalize();
+    Ok(data)
+}
@@ -2,9 +2,10 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::color::Color;
+use crate::{color::Color, unknown::UnknownFields};
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 /// Subject, normally related to a [`crate::school::bells::BellData::Class`].
 pub struct Subject {
     /// Unique identifier.
@@ -18,4 +19,7 @@ pub struct Subject {
     #[serde(rename = "iconName")]
     /// SF Symbols icon of the subject.
     pub icon: String,
+    /// Unknown fields, kept so that they are saved unchanged.
+    #[serde(flatten)]
+    pub unknown: UnknownFields,
 }
@@ -0,0 +1,51 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "diff")]
+use diff::Diff;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+/// Fields of an object in Subjective data which aren't known to this version of the library, like
+/// fields added by a newer version of the Subjective app.
+///
+/// They are kept when the data is deserialised and serialised unchanged, so that loading and
+/// saving data doesn't lose them.
+pub struct UnknownFields(pub BTreeMap<String, Value>);
+
+impl UnknownFields {
+    /// Create an empty set of unknown fields.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Returns `true` if there are no unknown fields.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(feature = "diff")]
+impl Diff for UnknownFields {
+    type Repr = Option<Self>;
+
+    fn diff(&self, other: &Self) -> Self::Repr {
+        (self != other).then(|| other.clone())
+    }
+
+    fn apply(&mut self, diff: &Self::Repr) {
+        if let Some(other) = diff {
+            self.clone_from(other);
+        }
+    }
+
+    fn identity() -> Self {
+        Self::new()
+    }
+}
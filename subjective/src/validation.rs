@@ -0,0 +1,330 @@
+#![cfg(feature = "std")]
+
+use core::fmt::{self, Display, Formatter};
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveTime};
+use uuid::Uuid;
+
+use crate::{
+    Subjective,
+    color::Color,
+    school::{Day, bells::BellData, overrides::Replacement},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Severity of a [`Diagnostic`].
+pub enum Severity {
+    /// The data is unusual, but bells can still be found correctly.
+    Warning,
+    /// The data is invalid, and bells may be found incorrectly or not at all.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Problem found in Subjective data by [`Subjective::validate`].
+pub enum Issue {
+    /// The school has no week variants.
+    NoWeeks,
+    /// No week variant is included in the automatic cycle.
+    NoCyclicalWeeks,
+    /// A week variant has no bells on any day.
+    EmptyWeek,
+    /// A week variant doesn't have between 5 and 7 days.
+    WrongDayCount(usize),
+    /// The bells of a day aren't in ascending order, starting at the first bell out of order.
+    UnsortedBells(NaiveTime),
+    /// A class refers to a subject which doesn't exist.
+    DanglingSubject(Uuid),
+    /// A bell has a type which isn't known to this version of the library. The type is kept, but
+    /// the bell is shown without an icon.
+    UnknownBellType(String),
+    /// Two or more entries of the same kind share a UUID. Nil UUIDs, which are assigned by
+    /// [`crate::school::School::assign_ids`], are not considered.
+    DuplicateId(Uuid),
+    /// A component of a color is outside of `0_f32..=1_f32`.
+    ColorOutOfRange(&'static str, f32),
+    /// A link doesn't point to an HTTP or HTTPS URL.
+    InvalidLink(String),
+    /// The timezone of the school isn't a known IANA timezone.
+    InvalidTimezone(String),
+    /// A week variant index doesn't refer to a week variant of the school.
+    VariantOutOfRange(usize),
+    /// The cycle anchor refers to a week variant which isn't cyclical, so no variant can be found
+    /// from the cycle.
+    NonCyclicalAnchor(usize),
+    /// A date range ends before it starts.
+    InvertedDateRange(NaiveDate, NaiveDate),
+}
+
+impl Issue {
+    /// Get the severity of the issue.
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Self::EmptyWeek
+            | Self::DuplicateId(_)
+            | Self::ColorOutOfRange(..)
+            | Self::InvalidLink(_)
+            | Self::UnknownBellType(_) => Severity::Warning,
+            Self::NoWeeks
+            | Self::NoCyclicalWeeks
+            | Self::WrongDayCount(_)
+            | Self::UnsortedBells(_)
+            | Self::DanglingSubject(_)
+            | Self::InvalidTimezone(_)
+            | Self::VariantOutOfRange(_)
+            | Self::NonCyclicalAnchor(_)
+            | Self::InvertedDateRange(..) => Severity::Error,
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoWeeks => write!(f, "There are no week variants."),
+            Self::NoCyclicalWeeks => write!(
+                f,
+                "No week variant is cyclical, so bells can only be found on activated dates."
+            ),
+            Self::EmptyWeek => write!(f, "There are no bells in the week variant."),
+            Self::WrongDayCount(days) => {
+                write!(f, "There are {days} days, but there should be 5 to 7.")
+            }
+            Self::UnsortedBells(time) => write!(
+                f,
+                "Bells aren't in ascending order, starting at {}.",
+                time.format("%-I:%M %p")
+            ),
+            Self::DanglingSubject(id) => write!(f, "No subject found matching \"{id}\"."),
+            Self::UnknownBellType(name) => write!(f, "The bell type \"{name}\" isn't known."),
+            Self::DuplicateId(id) => write!(f, "The UUID \"{id}\" is used more than once."),
+            Self::ColorOutOfRange(component, value) => write!(
+                f,
+                "The {component} component {value} is outside of the range `0..=1`."
+            ),
+            Self::InvalidLink(destination) => {
+                write!(f, "\"{destination}\" isn't an HTTP or HTTPS URL.")
+            }
+            Self::InvalidTimezone(timezone) => {
+                write!(f, "\"{timezone}\" isn't a known timezone.")
+            }
+            Self::VariantOutOfRange(variant) => {
+                write!(f, "There is no week variant with index {variant}.")
+            }
+            Self::NonCyclicalAnchor(variant) => write!(
+                f,
+                "The week variant with index {variant} isn't cyclical, so it can't anchor the cycle."
+            ),
+            Self::InvertedDateRange(start, end) => {
+                write!(
+                    f,
+                    "The date range ends on {end}, before it starts on {start}."
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Problem found in Subjective data by [`Subjective::validate`], with where it was found.
+pub struct Diagnostic {
+    /// Where the issue was found, like `Week "Week A", Mon`.
+    pub location: String,
+    /// The issue which was found.
+    pub issue: Issue,
+}
+
+impl Diagnostic {
+    /// Get the severity of the diagnostic.
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.issue.severity()
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.issue)
+    }
+}
+
+#[derive(Default)]
+struct Diagnostics(Vec<Diagnostic>);
+
+impl Diagnostics {
+    fn push(&mut self, location: impl Into<String>, issue: Issue) {
+        self.0.push(Diagnostic {
+            location: location.into(),
+            issue,
+        });
+    }
+
+    fn check_ids(&mut self, location: &str, ids: impl IntoIterator<Item = Uuid>) {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for id in ids {
+            if !id.is_nil() && !seen.insert(id) && reported.insert(id) {
+                self.push(location, Issue::DuplicateId(id));
+            }
+        }
+    }
+
+    fn check_day(&mut self, location: &str, day: &Day, subjects: &HashSet<Uuid>) {
+        if let Some(bell) = day
+            .windows(2)
+            .find(|bells| bells[1].time < bells[0].time)
+            .map(|bells| &bells[1])
+        {
+            self.push(location, Issue::UnsortedBells(bell.time));
+        }
+        for bell in day {
+            match &bell.bell_data {
+                Some(BellData::Class { subject_id, .. }) if !subjects.contains(subject_id) => {
+                    self.push(
+                        format!("{location}, bell \"{}\"", bell.name),
+                        Issue::DanglingSubject(*subject_id),
+                    );
+                }
+                Some(BellData::Other { name }) => self.push(
+                    format!("{location}, bell \"{}\"", bell.name),
+                    Issue::UnknownBellType(name.clone()),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_variant(&mut self, location: &str, variant: usize, weeks: usize) {
+        if variant >= weeks {
+            self.push(location, Issue::VariantOutOfRange(variant));
+        }
+    }
+
+    fn check_range(&mut self, location: &str, start: NaiveDate, end: NaiveDate) {
+        if end < start {
+            self.push(location, Issue::InvertedDateRange(start, end));
+        }
+    }
+
+    fn check_color(&mut self, location: &str, color: Color) {
+        for (component, value) in [
+            ("red", color.red),
+            ("green", color.green),
+            ("blue", color.blue),
+        ] {
+            if !(0_f32..=1_f32).contains(&value) {
+                self.push(location, Issue::ColorOutOfRange(component, value));
+            }
+        }
+    }
+}
+
+impl Subjective {
+    /// Check the data for problems which would cause bells to be found incorrectly or formatted
+    /// with errors, like unsorted bells or classes referring to subjects which don't exist.
+    /// Returns an empty list if no problems are found.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Diagnostics::default();
+        let school = &self.school;
+        let weeks = school.bell_times.len();
+        let subjects = self
+            .subjects
+            .iter()
+            .map(|subject| subject.id)
+            .collect::<HashSet<_>>();
+        if weeks == 0 {
+            diagnostics.push("School", Issue::NoWeeks);
+        } else if !school.bell_times.iter().any(|week| week.cyclical) {
+            diagnostics.push("School", Issue::NoCyclicalWeeks);
+        }
+        diagnostics.check_ids("Weeks", school.bell_times.iter().map(|week| week.id));
+        for week in &school.bell_times {
+            let location = format!("Week \"{}\"", week.name);
+            if let Some(days) = week.days.loaded_length
+                && !(5..=7).contains(&days)
+            {
+                diagnostics.push(&location, Issue::WrongDayCount(days));
+            }
+            if week.days.bells().next().is_none() {
+                diagnostics.push(&location, Issue::EmptyWeek);
+            }
+            for (weekday, day) in week.days.iter() {
+                diagnostics.check_day(&format!("{location}, {weekday}"), day, &subjects);
+            }
+            diagnostics.check_ids(&location, week.days.bells().map(|bell| bell.id));
+        }
+        for day_override in &school.overrides {
+            let location = format!("Override on {}", day_override.date);
+            match &day_override.replacement {
+                Replacement::Bells { bells } => {
+                    diagnostics.check_day(&location, bells, &subjects);
+                }
+                Replacement::Weekday {
+                    variant: Some(variant),
+                    ..
+                } => diagnostics.check_variant(&location, *variant, weeks),
+                Replacement::Weekday { variant: None, .. } => {}
+            }
+        }
+        for activation in &school.variant_activations {
+            let location = format!("Activation from {}", activation.start);
+            diagnostics.check_variant(&location, activation.variant, weeks);
+            diagnostics.check_range(&location, activation.start, activation.end);
+        }
+        if let Some(anchor) = school.cycle_anchor {
+            diagnostics.check_variant("Cycle anchor", anchor.variant, weeks);
+            if school
+                .bell_times
+                .get(anchor.variant)
+                .is_some_and(|week| !week.cyclical)
+            {
+                diagnostics.push("Cycle anchor", Issue::NonCyclicalAnchor(anchor.variant));
+            }
+        }
+        for term in &school.calendar.terms {
+            diagnostics.check_range(&format!("Term \"{}\"", term.name), term.start, term.end);
+        }
+        for holiday in &school.calendar.holidays {
+            diagnostics.check_range(
+                &format!("Holiday \"{}\"", holiday.name),
+                holiday.start,
+                holiday.end,
+            );
+        }
+        if let Some(timezone) = &school.timezone
+            && school.tz().is_none()
+        {
+            diagnostics.push("School", Issue::InvalidTimezone(timezone.clone()));
+        }
+        for link in &school.links {
+            let destination = link.destination.trim();
+            if !(destination.starts_with("https://") || destination.starts_with("http://"))
+                || destination.contains(char::is_whitespace)
+            {
+                diagnostics.push(
+                    format!("Link \"{}\"", link.name),
+                    Issue::InvalidLink(link.destination.clone()),
+                );
+            }
+        }
+        diagnostics.check_ids("Links", school.links.iter().map(|link| link.id));
+        diagnostics.check_ids("Notices", school.notices.iter().map(|notice| notice.id));
+        diagnostics.check_ids("Subjects", self.subjects.iter().map(|subject| subject.id));
+        for subject in &self.subjects {
+            diagnostics.check_color(&format!("Subject \"{}\"", subject.name), subject.color);
+        }
+        diagnostics.0
+    }
+}
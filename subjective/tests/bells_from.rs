@@ -0,0 +1,46 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::test_helper::load_data;
+
+#[test]
+fn bells_from_continues_over_weekends() {
+    let subjective = load_data();
+    let (date, bell_time) = subjective
+        .bells_from(
+            NaiveDate::from_ymd_opt(2021, 1, 1)
+                .unwrap()
+                .and_hms_opt(18, 0, 0)
+                .unwrap(),
+            0,
+        )
+        .next()
+        .unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2021, 1, 4).unwrap());
+    assert_eq!(bell_time.name, "Period 1");
+    assert_eq!(bell_time.time, NaiveTime::from_hms_opt(8, 40, 0).unwrap());
+}
+
+#[test]
+fn bells_before_continues_over_weekends() {
+    let subjective = load_data();
+    let mut bells = subjective.bells_before(
+        NaiveDate::from_ymd_opt(2021, 1, 4)
+            .unwrap()
+            .and_hms_opt(7, 0, 0)
+            .unwrap(),
+        0,
+    );
+    let (date, _) = bells.next().unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+    let mut last = None;
+    for (date, bell_time) in bells.take(20) {
+        if let Some((last_date, last_time)) = last {
+            assert!((date, bell_time.time) <= (last_date, last_time));
+        }
+        last = Some((date, bell_time.time));
+    }
+}
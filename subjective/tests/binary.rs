@@ -0,0 +1,77 @@
+#![cfg(feature = "binary")]
+
+use subjective::binary::{DecodeError, Sizes, sizes};
+
+/// Binary data of the timetable in `SOURCE`.
+const BYTES: [u8; 42] = [
+    83, 74, 84, 84, 1, 2, 5, 4, 1, 244, 164, 90, 1, 1, 3, 2, 224, 243, 1, 1, 1, 240, 143, 2, 3, 1,
+    0, 1, 240, 165, 3, 2, 0, 1, 2, 0, 1, 144, 253, 1, 1, 1,
+];
+
+const SIZES: Sizes = Sizes {
+    weeks: 2,
+    days: 5,
+    bells: 4,
+};
+
+#[cfg(feature = "std")]
+const SOURCE: &str = include_str!("test_helper/timetable.toml");
+
+#[cfg(feature = "std")]
+#[test]
+fn timetables_are_written() {
+    use subjective::{binary::to_binary, source::compile};
+
+    let data = compile(SOURCE).unwrap();
+    assert_eq!(to_binary(&data).unwrap(), BYTES);
+}
+
+#[test]
+fn sizes_are_read() {
+    assert_eq!(sizes(&BYTES).unwrap(), SIZES);
+    assert!(matches!(sizes(b"JSON"), Err(DecodeError::InvalidMagic)));
+}
+
+#[cfg(not(feature = "std"))]
+#[test]
+fn timetables_are_read_without_allocating() {
+    use chrono::{NaiveDate, NaiveTime};
+    use subjective::{
+        binary::from_binary,
+        school::{
+            Day, Week,
+            bells::{BellData, BellTime},
+        },
+    };
+
+    let mut weeks = [Week::default(), Week::default()];
+    let mut days: [Day; 8] = [&[]; 8];
+    let mut bells = [BellTime::EMPTY; 8];
+    let data = from_binary(&BYTES, &mut weeks, &mut days, &mut bells).unwrap();
+    let week_a = &data.school.bell_times[0];
+    assert_eq!(data.school.bell_times.len(), SIZES.weeks);
+    assert_eq!(week_a.days.len(), 3);
+    assert!(week_a.days[1].is_empty());
+    assert!(!week_a.days[2][0].enabled);
+    assert_eq!(data.school.cycle_anchor.unwrap().variant, 1);
+
+    let date_time = |day, hour| {
+        NaiveDate::from_ymd_opt(2026, 2, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    };
+    let bell = data.find_first_after(date_time(2, 9), 0).unwrap();
+    assert_eq!(bell.time, NaiveTime::from_hms_opt(9, 40, 0).unwrap());
+    assert_eq!(bell.bell_data, Some(BellData::Break));
+    let bell = data.find_first_after(date_time(10, 8), 0).unwrap();
+    assert_eq!(bell.bell_data, Some(BellData::Class {}));
+
+    let mut weeks = [Week::default(), Week::default()];
+    let mut days: [Day; 8] = [&[]; 8];
+    let mut bells = [BellTime::EMPTY; 3];
+    assert!(matches!(
+        from_binary(&BYTES, &mut weeks, &mut days, &mut bells),
+        Err(DecodeError::StorageTooSmall(SIZES))
+    ));
+}
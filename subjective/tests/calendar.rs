@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::NaiveDate;
+use subjective::{
+    Subjective,
+    school::calendar::{Holiday, NoSchoolReason, PupilFreeDay, Term},
+};
+
+use crate::test_helper::load_data;
+
+fn load_data_with_calendar() -> Subjective {
+    let mut subjective = load_data();
+    let calendar = &mut subjective.school.calendar;
+    calendar.terms.push(Term {
+        name: "Term 1".to_string(),
+        start: NaiveDate::from_ymd_opt(2021, 1, 27).unwrap(),
+        end: NaiveDate::from_ymd_opt(2021, 4, 1).unwrap(),
+    });
+    calendar.holidays.push(Holiday {
+        name: "Australia Day".to_string(),
+        start: NaiveDate::from_ymd_opt(2021, 1, 26).unwrap(),
+        end: NaiveDate::from_ymd_opt(2021, 1, 26).unwrap(),
+    });
+    calendar.pupil_free_days.push(PupilFreeDay {
+        name: "Staff Development Day".to_string(),
+        date: NaiveDate::from_ymd_opt(2021, 1, 27).unwrap(),
+    });
+    subjective
+}
+
+#[test]
+fn no_school_outside_of_terms() {
+    let subjective = load_data_with_calendar();
+    let date = NaiveDate::from_ymd_opt(2021, 1, 25).unwrap();
+    assert_eq!(
+        subjective.school.calendar.no_school_reason(date),
+        Some(NoSchoolReason::OutOfTerm)
+    );
+    assert!(matches!(subjective.get_day(date, 0), Ok(None)));
+    assert!(matches!(
+        subjective
+            .school
+            .calendar
+            .no_school_reason(date.succ_opt().unwrap()),
+        Some(NoSchoolReason::Holiday(_))
+    ));
+    assert!(matches!(
+        subjective
+            .school
+            .calendar
+            .no_school_reason(NaiveDate::from_ymd_opt(2021, 1, 27).unwrap()),
+        Some(NoSchoolReason::PupilFreeDay(_))
+    ));
+}
+
+#[test]
+fn bells_from_skips_days_without_school() {
+    let subjective = load_data_with_calendar();
+    let (date, _) = subjective
+        .bells_from(
+            NaiveDate::from_ymd_opt(2021, 1, 1)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            0,
+        )
+        .next()
+        .unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2021, 1, 28).unwrap());
+}
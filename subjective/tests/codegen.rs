@@ -0,0 +1,53 @@
+#[cfg(feature = "std")]
+const SOURCE: &str = include_str!("test_helper/timetable.toml");
+
+#[cfg(feature = "std")]
+#[test]
+fn timetables_are_generated() {
+    use subjective::{codegen::to_rust, source::compile};
+
+    let data = compile(SOURCE).unwrap();
+    assert_eq!(
+        to_rust(&data).unwrap(),
+        include_str!("codegen/timetable.rs")
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn timetables_outside_the_package_are_rejected() {
+    use subjective::codegen::{CodegenError, generate};
+
+    for file in [
+        "/tmp/timetable.toml",
+        "../timetable.toml",
+        "tests/../../timetable.toml",
+    ] {
+        assert!(matches!(generate(file), Err(CodegenError::InvalidPath(_))));
+    }
+}
+
+/// The timetable in `SOURCE`, as checked by `timetables_are_generated`.
+#[cfg(not(feature = "std"))]
+static TIMETABLE: subjective::Subjective<'static, 'static, 'static> =
+    include!("codegen/timetable.rs");
+
+#[cfg(not(feature = "std"))]
+#[test]
+fn generated_timetables_are_static() {
+    use chrono::{NaiveDate, NaiveTime};
+    use subjective::school::bells::BellData;
+
+    let school = &TIMETABLE.school;
+    assert_eq!(school.bell_times.len(), 2);
+    assert_eq!(school.bell_times[1].days.len(), 2);
+    assert_eq!(school.cycle_anchor.unwrap().variant, 1);
+
+    let date_time = NaiveDate::from_ymd_opt(2026, 2, 2)
+        .unwrap()
+        .and_hms_opt(9, 0, 0)
+        .unwrap();
+    let bell = TIMETABLE.find_first_after(date_time, 0).unwrap();
+    assert_eq!(bell.time, NaiveTime::from_hms_opt(9, 40, 0).unwrap());
+    assert_eq!(bell.bell_data, Some(BellData::Break));
+}
@@ -0,0 +1,30 @@
+{
+    #[allow(unused_imports)]
+    use ::subjective::{Subjective, chrono::{NaiveDate, NaiveTime}, school::{School, Week, bells::{BellData, BellTime}, cycle::CycleAnchor}};
+    const fn time(hour: u32, minute: u32, second: u32) -> NaiveTime {
+        match NaiveTime::from_hms_opt(hour, minute, second) { Some(time) => time, None => panic!("invalid time") }
+    }
+    Subjective {
+        school: School {
+            bell_times: &[
+                Week { cyclical: true, days: &[
+                    &[
+                        BellTime { time: time(8, 40, 0), bell_data: Some(BellData::Class {}), enabled: true },
+                        BellTime { time: time(9, 40, 0), bell_data: Some(BellData::Break), enabled: true },
+                    ],
+                    &[],
+                    &[
+                        BellTime { time: time(15, 0, 0), bell_data: Some(BellData::Time), enabled: false },
+                    ],
+                ] },
+                Week { cyclical: true, days: &[
+                    &[],
+                    &[
+                        BellTime { time: time(9, 0, 0), bell_data: Some(BellData::Class {}), enabled: true },
+                    ],
+                ] },
+            ],
+            cycle_anchor: Some(CycleAnchor { date: match NaiveDate::from_ymd_opt(2026, 1, 26) { Some(date) => date, None => panic!("invalid date") }, variant: 1 }),
+        },
+    }
+}
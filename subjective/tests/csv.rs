@@ -0,0 +1,87 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::NaiveTime;
+use serde_json::json;
+use subjective::{
+    export::csv::to_csv,
+    import::csv::{ImportError, from_csv},
+    school::bells::BellData,
+};
+
+use crate::test_helper::load_data;
+
+#[test]
+fn sheets_round_trip() {
+    let mut subjective = load_data();
+    subjective.school.bell_times[0].days.monday[1].icon = Some("bell.badge".to_string());
+    subjective.school.bell_times[0].days.monday[2]
+        .unknown
+        .0
+        .insert("reminder".to_string(), json!(5));
+    subjective.subjects[0]
+        .unknown
+        .0
+        .insert("teacher".to_string(), json!("Ms. Smith"));
+    let mut bells = Vec::new();
+    let mut subjects = Vec::new();
+    to_csv(&subjective, &mut bells, &mut subjects).unwrap();
+    let bells = String::from_utf8(bells).unwrap();
+    assert!(bells.starts_with("variant,weekday,time,name,type,subject,location,enabled,icon,id\n"));
+    assert!(
+        bells.contains(
+            ",Mon,08:40,Period 1,class,Yr 10 Information & Software Technology,C4,true,,"
+        )
+    );
+    assert!(bells.contains(",true,bell.badge,"));
+    let imported = from_csv(subjective.clone(), bells.as_bytes(), subjects.as_slice()).unwrap();
+    assert_eq!(imported.school.bell_times, subjective.school.bell_times);
+    assert_eq!(imported.subjects, subjective.subjects);
+    let ids = |subjective: &subjective::Subjective| {
+        subjective.school.bell_times[0]
+            .days
+            .bells()
+            .map(|bell| bell.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&imported), ids(&subjective));
+}
+
+#[test]
+fn hand_written_sheets_are_imported() {
+    let mut data = load_data();
+    data.school.bell_times.clear();
+    let subjects = "name,red,green,blue,locations\nMaths,1,0,0,A1; A2\n";
+    let bells = "variant,weekday,time,name,type,subject,location\n\
+        Week A,Monday,9:00,Period 1,Class,Maths,A1\n\
+        Week A,Mon,10:00,End,time,,\n";
+    let imported = from_csv(data.clone(), bells.as_bytes(), subjects.as_bytes()).unwrap();
+    assert_eq!(imported.subjects[0].locations, vec!["A1", "A2"]);
+    let week = &imported.school.bell_times[0];
+    assert_eq!(week.name, "Week A");
+    assert!(week.cyclical);
+    assert_eq!(week.days.monday.len(), 2);
+    assert_eq!(
+        week.days.monday[0].time,
+        NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+    );
+    assert!(week.days.monday[0].enabled);
+    assert_eq!(
+        week.days.monday[0].bell_data,
+        Some(BellData::Class {
+            subject_id: imported.subjects[0].id,
+            location: "A1".to_string(),
+        })
+    );
+    let bells = "variant,weekday,time,name,type,subject\nWeek A,Tue,9:00,Period 1,class,English\n";
+    assert!(matches!(
+        from_csv(data.clone(), bells.as_bytes(), subjects.as_bytes()),
+        Err(ImportError::SubjectNotFound(2, subject)) if subject == "English"
+    ));
+    data.school.bell_times = vec![imported.school.bell_times[0].clone(); 2];
+    assert!(matches!(
+        from_csv(data, bells.as_bytes(), subjects.as_bytes()),
+        Err(ImportError::DuplicateVariant(variant)) if variant == "Week A"
+    ));
+}
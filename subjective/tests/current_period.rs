@@ -0,0 +1,36 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::{NaiveDate, NaiveTime, TimeDelta};
+use subjective::FindBellError;
+
+use crate::test_helper::load_data;
+
+#[test]
+fn current_period_works() {
+    let subjective = load_data();
+    let date_time = NaiveDate::from_ymd_opt(2021, 1, 1)
+        .unwrap()
+        .and_hms_opt(10, 30, 0)
+        .unwrap();
+    let period = subjective.current_period(date_time, 0).unwrap();
+    assert_eq!(period.bell.name, "Period 3");
+    assert_eq!(period.start, NaiveTime::from_hms_opt(10, 26, 0).unwrap());
+    assert_eq!(period.end, NaiveTime::from_hms_opt(11, 6, 0).unwrap());
+    assert_eq!(period.elapsed(date_time.time()), TimeDelta::minutes(4));
+    assert_eq!(period.remaining(date_time.time()), TimeDelta::minutes(36));
+}
+
+#[test]
+fn no_period_after_last_bell() {
+    let subjective = load_data();
+    let date_time = NaiveDate::from_ymd_opt(2021, 1, 1)
+        .unwrap()
+        .and_hms_opt(18, 0, 0)
+        .unwrap();
+    assert!(matches!(
+        subjective.current_period(date_time, 0),
+        Err(FindBellError::NoBellFound)
+    ));
+}
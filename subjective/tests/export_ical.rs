@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::{DateTime, NaiveDate};
+use subjective::{
+    export::ical::{ExportError, to_ical},
+    school::calendar::Holiday,
+};
+
+use crate::test_helper::load_data;
+
+fn export(subjective: &subjective::Subjective, to: NaiveDate) -> Result<String, ExportError> {
+    to_ical(
+        subjective,
+        NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+        to,
+        0,
+        DateTime::UNIX_EPOCH,
+    )
+}
+
+#[test]
+fn weekly_classes_are_combined() {
+    let subjective = load_data();
+    let ical = export(&subjective, NaiveDate::from_ymd_opt(2021, 1, 31).unwrap()).unwrap();
+    assert!(ical.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ical.ends_with("END:VCALENDAR\r\n"));
+    assert!(ical.contains(
+        "DTSTART:20210104T084000\r\nDTEND:20210104T091700\r\nRRULE:FREQ=WEEKLY;INTERVAL=1;COUNT=4;BYDAY=MO\r\nSUMMARY:Yr 10 Information & Software Technology\r\nLOCATION:C4\r\n"
+    ));
+    assert!(ical.lines().all(|line| line.len() <= 75));
+}
+
+#[test]
+fn holidays_interrupt_repetition() {
+    let mut subjective = load_data();
+    subjective.school.calendar.holidays.push(Holiday {
+        name: "Holiday".to_string(),
+        start: NaiveDate::from_ymd_opt(2021, 1, 11).unwrap(),
+        end: NaiveDate::from_ymd_opt(2021, 1, 15).unwrap(),
+    });
+    let ical = export(&subjective, NaiveDate::from_ymd_opt(2021, 1, 31).unwrap()).unwrap();
+    assert!(ical.contains("DTSTART:20210104T084000\r\nDTEND:20210104T091700\r\nSUMMARY:"));
+    assert!(ical.contains(
+        "DTSTART:20210118T084000\r\nDTEND:20210118T091700\r\nRRULE:FREQ=WEEKLY;INTERVAL=1;COUNT=2;BYDAY=MO\r\n"
+    ));
+    assert!(matches!(
+        export(&subjective, NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()),
+        Err(ExportError::InvertedDateRange(..))
+    ));
+}
+
+#[test]
+fn timezones_are_described() {
+    let mut subjective = load_data();
+    subjective.school.timezone = Some("Australia/Sydney".to_string());
+    let ical = export(&subjective, NaiveDate::from_ymd_opt(2021, 5, 31).unwrap()).unwrap();
+    let timezone = ical
+        .find("BEGIN:VTIMEZONE\r\nTZID:Australia/Sydney\r\n")
+        .unwrap();
+    assert!(timezone < ical.find("BEGIN:VEVENT").unwrap());
+    assert!(ical.contains(
+        "BEGIN:DAYLIGHT\r\nDTSTART:20210103T110000\r\nTZOFFSETFROM:+1100\r\nTZOFFSETTO:+1100\r\nTZNAME:AEDT\r\nEND:DAYLIGHT\r\n"
+    ));
+    assert!(ical.contains(
+        "BEGIN:STANDARD\r\nDTSTART:20210404T030000\r\nTZOFFSETFROM:+1100\r\nTZOFFSETTO:+1000\r\nTZNAME:AEST\r\nEND:STANDARD\r\nEND:VTIMEZONE\r\n"
+    ));
+    assert!(ical.contains("DTSTART;TZID=Australia/Sydney:20210104T084000\r\n"));
+    assert_eq!(ical.matches("BEGIN:VTIMEZONE").count(), 1);
+}
@@ -4,6 +4,7 @@ mod test_helper;
 
 use chrono::{NaiveDate, NaiveTime};
 use subjective::school::bells::{BellData, BellTime};
+use subjective::unknown::UnknownFields;
 use uuid::{uuid, Uuid};
 
 use crate::test_helper::load_data;
@@ -31,6 +32,8 @@ fn find_first_after_works() {
                 location: "H1".to_string()
             }),
             enabled: true,
+            icon: None,
+            unknown: UnknownFields::new(),
         }
     );
 }
@@ -58,6 +61,8 @@ fn find_first_before_works() {
                 location: "G16".to_string()
             }),
             enabled: true,
+            icon: None,
+            unknown: UnknownFields::new(),
         }
     );
 }
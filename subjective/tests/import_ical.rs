@@ -0,0 +1,154 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Weekday};
+use subjective::{
+    Subjective,
+    export::ical::to_ical,
+    import::ical::{ImportError, from_ical},
+    school::{bells::BellData, cycle::CycleAnchor},
+};
+
+use crate::test_helper::load_data;
+
+fn classes(subjective: &Subjective, variant: usize, weekday: Weekday) -> Vec<(NaiveTime, String)> {
+    subjective.school.bell_times[variant].days[weekday]
+        .iter()
+        .filter_map(|bell| match &bell.bell_data {
+            Some(BellData::Class { subject_id, .. }) => Some((
+                bell.time,
+                subjective.get_subject(*subject_id).unwrap().name.clone(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn round_trip(subjective: &Subjective) -> Subjective {
+    let ics = to_ical(
+        subjective,
+        NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+        NaiveDate::from_ymd_opt(2021, 1, 31).unwrap(),
+        0,
+        DateTime::UNIX_EPOCH,
+    )
+    .unwrap();
+    from_ical(&ics).unwrap()
+}
+
+#[test]
+fn exported_timetables_are_imported() {
+    let subjective = load_data();
+    let imported = round_trip(&subjective);
+    assert_eq!(imported.school.bell_times.len(), 1);
+    assert_eq!(imported.school.name, subjective.school.name);
+    for weekday in [Weekday::Mon, Weekday::Wed, Weekday::Fri] {
+        assert_eq!(
+            classes(&imported, 0, weekday),
+            classes(&subjective, 0, weekday)
+        );
+    }
+    assert!(imported.validate().is_empty());
+}
+
+#[test]
+fn cycles_are_inferred() {
+    let mut subjective = load_data();
+    let mut week_b = subjective.school.bell_times[0].clone();
+    week_b.name = "Week B".to_string();
+    week_b.days.monday.swap(0, 1);
+    let (first, second) = week_b.days.monday.split_at_mut(1);
+    core::mem::swap(&mut first[0].time, &mut second[0].time);
+    subjective.school.bell_times.push(week_b);
+    subjective.school.cycle_anchor = Some(CycleAnchor {
+        date: NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+        variant: 0,
+    });
+    let imported = round_trip(&subjective);
+    assert_eq!(imported.school.bell_times.len(), 2);
+    for variant in 0..2 {
+        assert_eq!(
+            classes(&imported, variant, Weekday::Mon),
+            classes(&subjective, variant, Weekday::Mon)
+        );
+    }
+    let week_b_monday = NaiveDate::from_ymd_opt(2021, 2, 22).unwrap();
+    assert_eq!(imported.current_variant(week_b_monday, 0), Some(1));
+}
+
+#[test]
+fn calendars_from_other_applications_are_imported() {
+    let ics = "BEGIN:VCALENDAR\r\n\
+        BEGIN:VEVENT\r\n\
+        DTSTART;TZID=Australia/Sydney:20260202T090000\r\n\
+        DURATION:PT1H\r\n\
+        RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20260301T000000Z\r\n\
+        EXDATE;TZID=Australia/Sydney:20260204T090000\r\n\
+        SUMMARY:Maths\\, Advanced\r\n\
+        LOCATION:Room\r\n  12\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+    let imported = from_ical(ics).unwrap();
+    assert_eq!(
+        imported.school.timezone.as_deref(),
+        Some("Australia/Sydney")
+    );
+    assert_eq!(imported.subjects[0].name, "Maths, Advanced");
+    assert_eq!(imported.subjects[0].locations, vec!["Room 12".to_string()]);
+    let wednesday = &imported.school.bell_times[0].days.wednesday;
+    assert_eq!(wednesday.len(), 2);
+    assert_eq!(
+        wednesday[1].time,
+        NaiveTime::from_hms_opt(10, 0, 0).unwrap()
+    );
+    assert!(matches!(
+        from_ical("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n"),
+        Err(ImportError::NoEvents)
+    ));
+}
+
+#[test]
+fn moved_and_cancelled_events_are_imported() {
+    let ics = "BEGIN:VCALENDAR\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:maths\r\n\
+        DTSTART:20260202T090000\r\n\
+        DURATION:PT1H\r\n\
+        RRULE:FREQ=WEEKLY;COUNT=2\r\n\
+        SUMMARY:Maths\r\n\
+        BEGIN:VALARM\r\n\
+        ACTION:DISPLAY\r\n\
+        SUMMARY:Reminder\r\n\
+        DURATION:PT15M\r\n\
+        END:VALARM\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:maths\r\n\
+        RECURRENCE-ID:20260202T090000\r\n\
+        DTSTART:20260202T110000\r\n\
+        DTEND:20260202T120000\r\n\
+        SUMMARY:Maths\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:sport\r\n\
+        DTSTART:20260203T090000\r\n\
+        DURATION:PT1H\r\n\
+        SUMMARY:Sport\r\n\
+        STATUS:CANCELLED\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+    let imported = from_ical(ics).unwrap();
+    assert_eq!(imported.subjects.len(), 1);
+    let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+    assert_eq!(
+        classes(&imported, 0, Weekday::Mon),
+        vec![(time(11), "Maths".to_string())]
+    );
+    assert_eq!(
+        classes(&imported, 1, Weekday::Mon),
+        vec![(time(9), "Maths".to_string())]
+    );
+    assert_eq!(imported.school.bell_times[1].days.monday[1].time, time(10));
+    assert!(imported.school.bell_times[0].days.tuesday.is_empty());
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use serde_json::{Value, from_str, json};
+use subjective::{
+    Subjective,
+    migration::{CURRENT_VERSION, Migration, MigrationError, detect_version, migrate_school},
+};
+
+use crate::test_helper::load_data;
+
+const DATA: &str = include_str!("Timetable and Subjects.subjective");
+
+#[test]
+fn current_data_is_unchanged() {
+    let (data, migrations) = Subjective::from_json(DATA).unwrap();
+    assert!(migrations.is_empty());
+    assert_eq!(data.school.version, CURRENT_VERSION.to_string());
+    assert_eq!(data.school.bell_times, load_data().school.bell_times);
+}
+
+#[test]
+fn migrates_version_1() {
+    let mut value: Value = from_str(DATA).unwrap();
+    let school = value["school"].as_object_mut().unwrap();
+    let days = school["bellTimes"][0]["days"].take();
+    school.insert("bellTimes".to_string(), days);
+    school.remove("version");
+    assert_eq!(detect_version(&value["school"]).unwrap(), 1);
+
+    let (data, migrations) = Subjective::from_json(&value.to_string()).unwrap();
+    assert_eq!(
+        migrations,
+        vec![Migration {
+            from: 1,
+            to: 2,
+            description: "Moved the bells into a week variant.",
+        }]
+    );
+    assert_eq!(data.school.version, "2");
+    let week = &data.school.bell_times[0];
+    assert_eq!(data.school.bell_times.len(), 1);
+    assert!(week.cyclical);
+    assert_eq!(week.days, load_data().school.bell_times[0].days);
+}
+
+#[test]
+fn detects_invalid_versions_from_layout() {
+    let school = json!({ "version": "two", "bellTimes": [] });
+    assert_eq!(detect_version(&school).unwrap(), CURRENT_VERSION);
+    let school = json!({ "version": "1.0", "bellTimes": [[]] });
+    assert_eq!(detect_version(&school).unwrap(), 1);
+}
+
+#[test]
+fn skips_version_1_migration_of_week_variants() {
+    let mut value: Value = from_str(DATA).unwrap();
+    value["school"]["version"] = json!("1");
+    let (data, migrations) = Subjective::from_json(&value.to_string()).unwrap();
+    assert!(migrations.is_empty());
+    assert_eq!(data.school.version, "2");
+    assert_eq!(data.school.bell_times, load_data().school.bell_times);
+    assert!(matches!(
+        migrate_school(&mut json!({ "version": "1" })),
+        Err(MigrationError::InvalidLayout(1, _))
+    ));
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::NaiveDate;
+use serde_json::{to_string, to_value};
+use subjective::{
+    Subjective,
+    normalization::ChangeKind,
+    school::overrides::{DayOverride, Replacement},
+};
+
+use crate::test_helper::load_data;
+
+#[test]
+fn ids_are_stable_between_loads() {
+    let mut first = load_data();
+    let second = load_data();
+    let ids = |subjective: &Subjective| {
+        subjective.school.bell_times[0]
+            .days
+            .bells()
+            .map(|bell| bell.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&first), ids(&second));
+    assert!(ids(&first).iter().all(|id| !id.is_nil()));
+    assert!(!first.school.bell_times[0].id.is_nil());
+    assert_eq!(first.normalize(), Vec::new());
+    assert_eq!(first.validate(), Vec::new());
+}
+
+#[test]
+fn ids_are_saved() {
+    let subjective = load_data();
+    let school = to_value(&subjective.school).unwrap();
+    let week = &school["bellTimes"][0];
+    assert_eq!(
+        week["id"],
+        to_value(subjective.school.bell_times[0].id).unwrap()
+    );
+    let bell = &week["days"][0][0];
+    assert_eq!(
+        bell["id"],
+        to_value(subjective.school.bell_times[0].days.monday[0].id).unwrap()
+    );
+    let (reloaded, _) = Subjective::from_json(&to_string(&subjective).unwrap()).unwrap();
+    assert_eq!(reloaded.school.bell_times, subjective.school.bell_times);
+}
+
+#[test]
+fn days_are_sorted_and_deduplicated() {
+    let mut subjective = load_data();
+    let monday = &mut subjective.school.bell_times[0].days.monday;
+    let bells = monday.len();
+    monday.reverse();
+    monday.push(monday[0].clone());
+    let kinds = subjective
+        .normalize()
+        .into_iter()
+        .map(|change| change.kind)
+        .filter(|kind| !matches!(kind, ChangeKind::AssignedId(_)))
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        kinds[..],
+        [ChangeKind::SortedBells, ChangeKind::RemovedDuplicateBell(_)]
+    ));
+    let monday = &subjective.school.bell_times[0].days.monday;
+    assert_eq!(monday.len(), bells);
+    assert!(monday.is_sorted_by_key(|bell| bell.time));
+}
+
+#[test]
+fn superseded_overrides_are_removed() {
+    let mut subjective = load_data();
+    let friday = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    for name in ["First", "Second"] {
+        subjective.school.overrides.push(DayOverride {
+            date: friday,
+            name: name.to_string(),
+            replacement: Replacement::Bells { bells: Vec::new() },
+        });
+    }
+    let override_name = subjective.get_override(friday).unwrap().name.clone();
+    subjective.normalize();
+    assert_eq!(subjective.school.overrides.len(), 1);
+    assert_eq!(subjective.get_override(friday).unwrap().name, override_name);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use subjective::school::{
+    bells::{BellData, BellTime},
+    calendar::Holiday,
+    overrides::{DayOverride, Replacement},
+};
+use subjective::unknown::UnknownFields;
+use uuid::Uuid;
+
+use crate::test_helper::load_data;
+
+#[test]
+fn weekday_overrides_replace_the_day() {
+    let mut subjective = load_data();
+    let friday = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    subjective.school.overrides.push(DayOverride {
+        date: friday,
+        name: "Friday follows Monday".to_string(),
+        replacement: Replacement::Weekday {
+            variant: None,
+            weekday: Weekday::Mon,
+        },
+    });
+    assert_eq!(
+        subjective.get_day(friday, 0).unwrap(),
+        subjective.get_variant_day(0, Weekday::Mon).unwrap()
+    );
+}
+
+#[test]
+fn bell_overrides_take_precedence_over_the_calendar() {
+    let mut subjective = load_data();
+    let saturday = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+    subjective.school.calendar.holidays.push(Holiday {
+        name: "Summer Holidays".to_string(),
+        start: NaiveDate::from_ymd_opt(2020, 12, 18).unwrap(),
+        end: NaiveDate::from_ymd_opt(2021, 1, 26).unwrap(),
+    });
+    let bells = vec![BellTime {
+        id: Uuid::new_v4(),
+        name: "Make-up Day".to_string(),
+        time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        bell_data: Some(BellData::Time),
+        enabled: true,
+        icon: None,
+        unknown: UnknownFields::new(),
+    }];
+    subjective.school.overrides.push(DayOverride {
+        date: saturday,
+        name: String::new(),
+        replacement: Replacement::Bells {
+            bells: bells.clone(),
+        },
+    });
+    assert_eq!(subjective.get_day(saturday, 0).unwrap(), Some(&bells));
+    let (date, bell_time) = subjective
+        .bells_from(
+            NaiveDate::from_ymd_opt(2021, 1, 1)
+                .unwrap()
+                .and_hms_opt(18, 0, 0)
+                .unwrap(),
+            0,
+        )
+        .next()
+        .unwrap();
+    assert_eq!(date, saturday);
+    assert_eq!(bell_time.name, "Make-up Day");
+}
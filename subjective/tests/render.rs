@@ -0,0 +1,52 @@
+#![cfg(feature = "std")]
+
+use subjective::{
+    render::{Grid, html::to_html, markdown::to_markdown},
+    source::compile,
+};
+
+const SOURCE: &str = r##"
+[school]
+name = "Example High School"
+
+[[subjects]]
+name = "English & Drama"
+color = "#ff0000"
+locations = ["C17"]
+
+[weeks."Week A"]
+monday = [
+    { time = "08:40", name = "Period 1", subject = "English & Drama" },
+    { time = "09:40", name = "Recess", type = "break" },
+    { time = "10:00", name = "Period 2", subject = "English & Drama", enabled = false },
+]
+tuesday = [{ time = "09:00", name = "Period 1", subject = "English & Drama" }]
+"##;
+
+#[test]
+fn timetables_are_rendered_as_grids() {
+    let data = compile(SOURCE).unwrap();
+    let grids = Grid::all(&data).unwrap();
+    assert_eq!(grids.len(), 1);
+    assert_eq!(grids[0].weekdays.len(), 5);
+    assert_eq!(grids[0].rows.len(), 3);
+
+    let cell = grids[0].rows[0][0].unwrap();
+    assert_eq!(cell.title(), "English & Drama");
+    assert_eq!(cell.location, "C17");
+    assert_eq!(cell.time(), "8:40 AM – 9:40 AM");
+    assert!(grids[0].rows[1][1].is_none());
+    assert_eq!(grids[0].rows[2][0].unwrap().time(), "10:00 AM");
+
+    let markdown = to_markdown(&data).unwrap();
+    assert!(markdown.contains("## Week A"));
+    assert!(markdown.contains("| Monday | Tuesday | Wednesday | Thursday | Friday |"));
+    assert!(markdown.contains("**English & Drama**<br>Period 1 · C17<br>8:40 AM – 9:40 AM"));
+    assert!(markdown.contains("~~**English & Drama**<br>Period 2 · C17<br>10:00 AM~~"));
+
+    let html = to_html(&data).unwrap();
+    assert!(html.contains("<title>Example High School</title>"));
+    assert!(html.contains("<td class=\"class\" style=\"--color: rgb(255 0 0)\">"));
+    assert!(html.contains("<strong>English &amp; Drama</strong>"));
+    assert!(html.contains("<td class=\"class disabled\""));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+use chrono::Weekday;
+use serde_json::{Value, from_str, json, to_string};
+use subjective::{Subjective, school::bells::BellData};
+
+const DATA: &str = include_str!("Timetable and Subjects.subjective");
+
+fn round_trip(value: &Value) -> Value {
+    let (data, _) = Subjective::from_json(&value.to_string()).unwrap();
+    from_str(&to_string(&data).unwrap()).unwrap()
+}
+
+/// Saves `value` after loading it, checking that only the assigned IDs were added and that saving
+/// again changes nothing.
+fn assert_saved_unchanged(value: &Value) {
+    let saved = round_trip(value);
+    assert_eq!(round_trip(&saved), saved);
+    let mut without_ids = saved;
+    for week in without_ids["school"]["bellTimes"].as_array_mut().unwrap() {
+        week.as_object_mut().unwrap().remove("id");
+        for day in week["days"].as_array_mut().unwrap() {
+            for bell in day.as_array_mut().unwrap() {
+                bell.as_object_mut().unwrap().remove("id");
+            }
+        }
+    }
+    assert_eq!(&without_ids, value);
+}
+
+#[test]
+fn saves_data_unchanged() {
+    let value: Value = from_str(DATA).unwrap();
+    assert_saved_unchanged(&value);
+}
+
+#[test]
+fn keeps_unknown_fields_and_bell_types() {
+    let mut value: Value = from_str(DATA).unwrap();
+    value["theme"] = json!("dark");
+    value["school"]["region"] = json!({ "state": "NSW" });
+    value["school"]["bellTimes"][0]["colour"] = json!("blue");
+    value["subjects"][0]["teacher"] = json!("Ms. Smith");
+    let bells = &mut value["school"]["bellTimes"][0]["days"][0];
+    bells[0]["reminder"] = json!(5);
+    bells[2]["bellType"] = json!({ "name": "Assembly", "iconName": "person.3.fill" });
+    bells[2]["location"] = json!("Hall");
+    bells[2]["subjectID"] = bells[0]["subjectID"].clone();
+    bells[5]["bellType"] = json!({
+        "name": "Break",
+        "iconName": "cup.and.saucer.fill",
+        "colour": "green",
+    });
+
+    let data: Subjective = from_str(&value.to_string()).unwrap();
+    let monday = &data.school.bell_times[0].days[Weekday::Mon];
+    assert_eq!(
+        monday[2].bell_data,
+        Some(BellData::Other {
+            name: "Assembly".to_string()
+        })
+    );
+    assert_eq!(monday[2].icon.as_deref(), Some("person.3.fill"));
+    assert_eq!(monday[5].bell_data, Some(BellData::Break));
+    assert_eq!(monday[5].icon.as_deref(), Some("cup.and.saucer.fill"));
+    assert_eq!(
+        monday[5].unknown.0["bellType"],
+        json!({ "colour": "green" })
+    );
+    assert_saved_unchanged(&value);
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "schema")]
+
+mod test_helper;
+
+use serde_json::{Value, to_value};
+use subjective::schema::{catalog_schema, data_schema};
+
+use crate::test_helper::load_data;
+
+#[test]
+fn data_matches_schema() {
+    let schema = to_value(data_schema()).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let raw: Value =
+        serde_json::from_str(include_str!("Timetable and Subjects.subjective")).unwrap();
+    assert!(validator.is_valid(&raw));
+    let subjective = load_data();
+    assert!(validator.is_valid(&to_value(&subjective).unwrap()));
+    let mut invalid = raw;
+    invalid["school"]["bellTimes"][0]["days"][0][0]["hour"] = "eight".into();
+    assert!(!validator.is_valid(&invalid));
+}
+
+#[test]
+fn catalogs_match_schema() {
+    let schema = to_value(catalog_schema()).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let school = to_value(load_data().school).unwrap();
+    assert!(validator.is_valid(&Value::Array(vec![school.clone(), school])));
+    assert!(!validator.is_valid(&to_value(load_data()).unwrap()));
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "std")]
+
+use chrono::{NaiveDate, NaiveTime};
+use subjective::{
+    school::bells::BellData,
+    source::{CompileError, compile},
+};
+
+const SOURCE: &str = r##"
+[school]
+name = "Example High School"
+timezone = "Australia/Sydney"
+cycle_anchor = { date = 2026-01-26, week = "Week B" }
+
+[[subjects]]
+name = "English"
+color = "#ff0000"
+locations = ["C17", "D6"]
+
+[[subjects]]
+name = "Maths"
+
+[weeks."Week B"]
+monday = [
+    { time = "08:40", name = "Period 1", subject = "English" },
+    { time = "09:40", name = "Recess", type = "break" },
+    { time = "15:00", name = "End", type = "time", enabled = false },
+]
+
+[weeks."Week A"]
+tue = [{ time = "09:00", name = "Period 1", subject = "Maths", location = "A1" }]
+
+[calendar]
+holidays = [{ name = "Easter", start = "2026-04-03", end = "2026-04-06" }]
+"##;
+
+#[test]
+fn sources_are_compiled() {
+    let subjective = compile(SOURCE).unwrap();
+    let school = &subjective.school;
+    assert_eq!(school.name, "Example High School");
+    assert_eq!(
+        school
+            .bell_times
+            .iter()
+            .map(|week| week.name.as_str())
+            .collect::<Vec<_>>(),
+        ["Week B", "Week A"]
+    );
+    assert_eq!(school.cycle_anchor.unwrap().variant, 0);
+    assert_eq!(
+        school.cycle_anchor.unwrap().date,
+        NaiveDate::from_ymd_opt(2026, 1, 26).unwrap()
+    );
+    let monday = &school.bell_times[0].days.monday;
+    assert_eq!(monday[0].time, NaiveTime::from_hms_opt(8, 40, 0).unwrap());
+    assert_eq!(
+        monday[0].bell_data,
+        Some(BellData::Class {
+            subject_id: subjective.subjects[0].id,
+            location: "C17".to_string(),
+        })
+    );
+    assert_eq!(monday[1].bell_data, Some(BellData::Break));
+    assert!(!monday[2].enabled);
+    assert_eq!(subjective.subjects[0].color.red, 1.);
+    assert_eq!(subjective.subjects[1].icon, "book.fill");
+    assert_eq!(school.calendar.holidays[0].name, "Easter");
+    assert!(subjective.validate().is_empty());
+    let again = compile(SOURCE).unwrap();
+    assert_eq!(again.subjects, subjective.subjects);
+    assert_eq!(again.school.bell_times, subjective.school.bell_times);
+    assert_eq!(again.school.bell_times[0].id, school.bell_times[0].id);
+}
+
+#[test]
+fn errors_have_lines() {
+    let source = SOURCE.replace("subject = \"Maths\"", "subject = \"Science\"");
+    assert!(matches!(
+        compile(&source),
+        Err(CompileError::SubjectNotFound(subject, 23)) if subject == "Science"
+    ));
+    let source = SOURCE.replace("\"09:40\"", "\"9.40am\"");
+    let error = compile(&source).unwrap_err().to_string();
+    assert!(error.contains("line 18"), "{error}");
+    assert!(error.contains("expected a time like \"08:40\""), "{error}");
+    let source = SOURCE.replace("type = \"break\"", "type = \"lunch\"");
+    assert!(matches!(compile(&source), Err(CompileError::ParseError(_))));
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::{DateTime, NaiveDate, TimeDelta};
+
+use crate::test_helper::load_data;
+
+#[test]
+fn times_are_converted_to_the_school_timezone() {
+    let mut subjective = load_data();
+    subjective.school.timezone = Some("Australia/Sydney".to_string());
+    let time = DateTime::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap();
+    assert_eq!(
+        subjective.school_time(&time),
+        NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(11, 0, 0)
+            .unwrap()
+    );
+}
+
+#[test]
+fn durations_account_for_daylight_saving_time() {
+    let mut subjective = load_data();
+    let date = NaiveDate::from_ymd_opt(2021, 10, 3).unwrap();
+    let from = date.and_hms_opt(1, 0, 0).unwrap();
+    let to = date.and_hms_opt(4, 0, 0).unwrap();
+    assert_eq!(subjective.duration_between(from, to), TimeDelta::hours(3));
+    subjective.school.timezone = Some("Australia/Sydney".to_string());
+    assert_eq!(subjective.duration_between(from, to), TimeDelta::hours(2));
+    assert_eq!(
+        subjective.localize(date.and_hms_opt(2, 30, 0).unwrap()),
+        subjective.localize(date.and_hms_opt(3, 30, 0).unwrap())
+    );
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use serde_json::{from_value, to_value};
+use subjective::{
+    Subjective,
+    validation::{Issue, Severity},
+};
+use uuid::Uuid;
+
+use crate::test_helper::load_data;
+
+#[test]
+fn valid_data_has_no_diagnostics() {
+    assert_eq!(load_data().validate(), Vec::new());
+}
+
+#[test]
+fn unsorted_bells_and_dangling_subjects_are_errors() {
+    let mut subjective = load_data();
+    let monday = &mut subjective.school.bell_times[0].days.monday;
+    monday.swap(0, 1);
+    subjective.subjects.retain(|subject| {
+        subject.id != Uuid::parse_str("9cb2053c-9f97-47b6-8f01-9bfb3c5b8abc").unwrap()
+    });
+    let diagnostics = subjective.validate();
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| matches!(diagnostic.issue, Issue::UnsortedBells(_)))
+    );
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| matches!(diagnostic.issue, Issue::DanglingSubject(_)))
+    );
+    assert!(
+        diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity() == Severity::Error)
+    );
+}
+
+#[test]
+fn schools_without_weeks_are_errors() {
+    let mut subjective = load_data();
+    subjective.school.bell_times.clear();
+    subjective.school.timezone = Some("Mars/Olympus".to_string());
+    let issues = subjective
+        .validate()
+        .into_iter()
+        .map(|diagnostic| diagnostic.issue)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![
+            Issue::NoWeeks,
+            Issue::InvalidTimezone("Mars/Olympus".to_string())
+        ]
+    );
+}
+
+#[test]
+fn truncated_weeks_are_errors() {
+    let mut json = to_value(load_data()).unwrap();
+    let days = json["school"]["bellTimes"][0]["days"]
+        .as_array_mut()
+        .unwrap();
+    days.truncate(3);
+    let subjective = from_value::<Subjective>(json).unwrap();
+    let diagnostics = subjective.validate();
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.issue == Issue::WrongDayCount(3))
+    );
+}
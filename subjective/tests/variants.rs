@@ -0,0 +1,113 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::NaiveDate;
+use subjective::{
+    FindBellError,
+    school::{activation::VariantActivation, cycle::CycleAnchor},
+    validation::Issue,
+};
+use uuid::Uuid;
+
+use crate::test_helper::load_data;
+
+#[test]
+fn non_cyclical_variants_are_skipped() {
+    let mut subjective = load_data();
+    let mut exam_week = subjective.school.bell_times[0].clone();
+    exam_week.name = "Exam Week".to_string();
+    exam_week.cyclical = false;
+    subjective.school.bell_times.insert(0, exam_week);
+    for day in 1..=31 {
+        let date = NaiveDate::from_ymd_opt(2021, 1, day).unwrap();
+        assert_eq!(subjective.current_variant(date, 0), Some(1));
+    }
+}
+
+#[test]
+fn activated_variants_take_precedence() {
+    let mut subjective = load_data();
+    let mut exam_week = subjective.school.bell_times[0].clone();
+    exam_week.cyclical = false;
+    subjective.school.bell_times.push(exam_week);
+    subjective
+        .school
+        .variant_activations
+        .push(VariantActivation {
+            variant: 1,
+            start: NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+            end: NaiveDate::from_ymd_opt(2021, 1, 8).unwrap(),
+        });
+    let current_variant =
+        |day| subjective.current_variant(NaiveDate::from_ymd_opt(2021, 1, day).unwrap(), 0);
+    assert_eq!(current_variant(3), Some(0));
+    assert_eq!(current_variant(4), Some(1));
+    assert_eq!(current_variant(8), Some(1));
+    assert_eq!(current_variant(9), Some(0));
+}
+
+#[test]
+fn no_cyclical_variants() {
+    let mut subjective = load_data();
+    subjective.school.bell_times[0].cyclical = false;
+    let date = NaiveDate::from_ymd_opt(2021, 1, 4).unwrap();
+    assert_eq!(subjective.current_variant(date, 0), None);
+    assert!(matches!(
+        subjective.get_day(date, 0),
+        Err(FindBellError::NoVariantFound)
+    ));
+}
+
+#[test]
+fn anchored_cycle_alternates_across_years() {
+    let mut subjective = load_data();
+    let mut week_b = subjective.school.bell_times[0].clone();
+    week_b.name = "Week B".to_string();
+    subjective.school.bell_times.push(week_b);
+    subjective.school.cycle_anchor = Some(CycleAnchor {
+        date: NaiveDate::from_ymd_opt(2026, 1, 28).unwrap(),
+        variant: 1,
+    });
+    let current_variant = |year, month, day| {
+        subjective.current_variant(NaiveDate::from_ymd_opt(year, month, day).unwrap(), 0)
+    };
+    assert_eq!(current_variant(2026, 1, 26), Some(1));
+    assert_eq!(current_variant(2026, 2, 1), Some(1));
+    assert_eq!(current_variant(2026, 2, 2), Some(0));
+    assert_eq!(current_variant(2026, 1, 25), Some(0));
+    assert_eq!(current_variant(2026, 12, 28), Some(1));
+    assert_eq!(current_variant(2027, 1, 4), Some(0));
+    assert_eq!(current_variant(2027, 1, 11), Some(1));
+}
+
+#[test]
+fn invalid_anchors_have_no_variant() {
+    let mut subjective = load_data();
+    let mut exam_week = subjective.school.bell_times[0].clone();
+    exam_week.id = Uuid::new_v4();
+    exam_week.cyclical = false;
+    subjective.school.bell_times.push(exam_week);
+    let date = NaiveDate::from_ymd_opt(2021, 1, 4).unwrap();
+    for variant in [1, 2] {
+        subjective.school.cycle_anchor = Some(CycleAnchor { date, variant });
+        assert_eq!(subjective.current_variant(date, 0), None);
+        assert!(matches!(
+            subjective.get_day(date, 0),
+            Err(FindBellError::NoVariantFound)
+        ));
+    }
+    let issues = subjective
+        .validate()
+        .into_iter()
+        .map(|diagnostic| diagnostic.issue)
+        .collect::<Vec<_>>();
+    assert_eq!(issues, vec![Issue::VariantOutOfRange(2)]);
+    subjective.school.cycle_anchor = Some(CycleAnchor { date, variant: 1 });
+    let issues = subjective
+        .validate()
+        .into_iter()
+        .map(|diagnostic| diagnostic.issue)
+        .collect::<Vec<_>>();
+    assert_eq!(issues, vec![Issue::NonCyclicalAnchor(1)]);
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "std")]
+
+mod test_helper;
+
+use chrono::{NaiveDate, Weekday};
+use serde_json::{Value, to_value};
+
+use crate::test_helper::load_data;
+
+#[test]
+fn weekends_without_bells_have_no_school() {
+    let subjective = load_data();
+    let saturday = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+    assert!(matches!(subjective.get_day(saturday, 0), Ok(None)));
+    assert!(matches!(
+        subjective.get_variant_day(0, Weekday::Sun),
+        Ok(None)
+    ));
+}
+
+#[test]
+fn weekend_school_days_are_found_and_serialised() {
+    let mut subjective = load_data();
+    let days_length = |subjective: &subjective::Subjective| {
+        to_value(&subjective.school.bell_times[0].days)
+            .unwrap()
+            .as_array()
+            .map(Vec::len)
+    };
+    assert_eq!(days_length(&subjective), Some(5));
+    let days = &mut subjective.school.bell_times[0].days;
+    days[Weekday::Sat] = days[Weekday::Mon].clone();
+    let saturday = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+    assert_eq!(
+        subjective.get_day(saturday, 0).unwrap(),
+        subjective.get_variant_day(0, Weekday::Mon).unwrap()
+    );
+    assert_eq!(days_length(&subjective), Some(6));
+    let json = to_value(&subjective.school).unwrap();
+    assert!(
+        matches!(json["bellTimes"][0]["days"][5], Value::Array(ref bells) if !bells.is_empty())
+    );
+}